@@ -0,0 +1,251 @@
+use crate::solution::Answer;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// Expected answers per day and part, stored as a small TOML subset.
+///
+/// ## Example
+/// ```toml
+/// [day1]
+/// part1 = 24000
+/// part2 = 45000
+///
+/// [day5]
+/// part1 = "CMZ"
+///
+/// [day10] # images are stored as multi-line strings
+/// part2 = """
+/// ##..##..
+/// ###...##"""
+///
+/// [day15] # other keys set parameters of the solvers
+/// part1 = 26
+/// row = 10
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8), Answer>,
+    params: BTreeMap<u8, Vec<(String, i64)>>,
+}
+
+impl Answers {
+    /// Load the answers from the given path, a missing file has no answers.
+    pub fn load(path: &str) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|error| format!("Unable to parse '{}': {}", path, error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Unable to read '{}': {}", path, error)),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_string())
+            .map_err(|error| format!("Unable to write '{}': {}", path, error))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.entries.get(&(day, part))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: Answer) {
+        self.entries.insert((day, part), answer);
+    }
+
+    /// Parameter values the answers of the day were computed with.
+    pub fn params(&self, day: u8) -> &[(String, i64)] {
+        self.params.get(&day).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn insert_param(&mut self, day: u8, name: &str, value: i64) {
+        let params = self.params.entry(day).or_default();
+        params.retain(|(key, _)| key != name);
+        params.push((name.to_string(), value));
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Expected answers for the puzzle inputs, see `verify --help`."
+        )?;
+        let days = self
+            .entries
+            .keys()
+            .map(|(day, _)| *day)
+            .chain(self.params.keys().copied())
+            .collect::<BTreeSet<u8>>();
+        for day in days {
+            writeln!(f, "\n[day{}]", day)?;
+            for part in 1..=2 {
+                if let Some(answer) = self.get(day, part) {
+                    write_answer(f, part, answer)?;
+                }
+            }
+            for (name, value) in self.params(day) {
+                writeln!(f, "{} = {}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_answer(f: &mut fmt::Formatter<'_>, part: u8, answer: &Answer) -> fmt::Result {
+    match answer {
+        Answer::Image(rows) => writeln!(
+            f,
+            "part{} = \"\"\"\n{}\"\"\"",
+            part,
+            rows.iter()
+                .map(|row| escape(row))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ),
+        Answer::Text(text) => writeln!(f, "part{} = \"{}\"", part, escape(text)),
+        answer => writeln!(f, "part{} = {}", part, answer),
+    }
+}
+
+fn escape(str: &str) -> String {
+    str.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl FromStr for Answers {
+    type Err = String;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+        let mut day = None;
+        let mut lines = str.lines().enumerate();
+        while let Some((index, line)) = lines.next() {
+            let error = |message: &str| format!("line {}: {}", index + 1, message);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("expected ']'"))?;
+                day = match name.strip_prefix("day").map(str::parse) {
+                    Some(Ok(n @ 1..=25)) => Some(n),
+                    _ => return Err(error(&format!("invalid table [{}]", name))),
+                };
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected 'key = value'"))?;
+            let day = day.ok_or_else(|| error("answer outside of a [dayN] table"))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                key if !key.is_empty() && key.chars().all(|c| c.is_ascii_lowercase()) => {
+                    let value = strip_comment(value).replace('_', "");
+                    let value = value
+                        .parse()
+                        .map_err(|_| error(&format!("invalid value '{}' for '{}'", value, key)))?;
+                    answers.insert_param(day, key, value);
+                    continue;
+                }
+                key => return Err(error(&format!("unknown key '{}'", key))),
+            };
+
+            let value = value.trim();
+            let answer = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                let mut text = rest.to_string();
+                while !text.ends_with("\"\"\"") || text.ends_with("\\\"\"\"") {
+                    let (_, line) = lines
+                        .next()
+                        .ok_or_else(|| error("unterminated multi-line string"))?;
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(line);
+                }
+                let text = unescape(&text[..text.len() - 3]);
+                Answer::Image(text.lines().map(String::from).collect())
+            } else if let Some(rest) = value.strip_prefix('"') {
+                let text = strip_comment(rest)
+                    .strip_suffix('"')
+                    .ok_or_else(|| error("unterminated string"))?;
+                Answer::Text(unescape(text))
+            } else {
+                let number = strip_comment(value).replace('_', "");
+                match (number.parse::<u64>(), number.parse::<i64>()) {
+                    (Ok(n), _) => Answer::Unsigned(n),
+                    (_, Ok(n)) => Answer::Signed(n),
+                    _ => return Err(error(&format!("invalid value '{}'", value))),
+                }
+            };
+            answers.insert(day, part, answer);
+        }
+        Ok(answers)
+    }
+}
+
+fn strip_comment(value: &str) -> &str {
+    match value.rfind('#') {
+        Some(index) if !value[index..].contains('"') => value[..index].trim(),
+        _ => value.trim(),
+    }
+}
+
+fn unescape(str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+    let mut chars = str.chars();
+    while let Some(char) = chars.next() {
+        match (char, chars.clone().next()) {
+            ('\\', Some(next @ ('\\' | '"'))) => {
+                result.push(next);
+                chars.next();
+            }
+            ('\\', Some('n')) => {
+                result.push('\n');
+                chars.next();
+            }
+            (char, _) => result.push(char),
+        }
+    }
+    result
+}
+
+#[test]
+fn test_round_trip() {
+    let mut answers = Answers::default();
+    answers.insert(1, 1, Answer::Unsigned(24_000));
+    answers.insert(5, 1, Answer::Text("C\"M\\Z".to_string()));
+    answers.insert(
+        10,
+        2,
+        Answer::Image(vec!["##..".to_string(), "..##".to_string()]),
+    );
+    answers.insert(21, 2, Answer::Signed(-301));
+    answers.insert_param(15, "row", 10);
+    answers.insert_param(22, "size", -4);
+    assert_eq!(answers.to_string().parse(), Ok(answers));
+}
+
+#[test]
+fn test_parse() {
+    let answers = "[day1]\npart1 = 1_000 # comment\npart2 = \"a # b\"\n"
+        .parse::<Answers>()
+        .unwrap();
+    assert_eq!(answers.get(1, 1), Some(&Answer::Unsigned(1000)));
+    assert_eq!(answers.get(1, 2), Some(&Answer::Text("a # b".to_string())));
+    assert!("part1 = 1".parse::<Answers>().is_err());
+    assert!("[day1]\npart3 = 1".parse::<Answers>().is_err());
+    assert!("[day1]\nrow = x".parse::<Answers>().is_err());
+
+    let answers = "[day15]\nrow = 10\nbound = 2_0 # example\n"
+        .parse::<Answers>()
+        .unwrap();
+    let params = [("row".to_string(), 10), ("bound".to_string(), 20)];
+    assert_eq!(answers.params(15), params);
+}
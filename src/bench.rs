@@ -0,0 +1,302 @@
+use crate::cli::BenchOptions;
+use crate::error::Error;
+use crate::inputs::Source;
+use crate::json::Json;
+use crate::pool;
+use crate::reader::Reader;
+use crate::runner::Failure;
+use crate::solution::Puzzle;
+use crate::summary::Summary;
+use crate::timing::format_duration;
+use std::time::{Duration, Instant};
+
+/// Summary statistics of repeated measurements, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos = samples
+            .iter()
+            .map(|sample| sample.as_nanos() as f64)
+            .collect::<Vec<f64>>();
+        nanos.sort_by(|a, b| a.total_cmp(b));
+
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / len;
+        let median = match nanos.len() % 2 {
+            0 => (nanos[nanos.len() / 2 - 1] + nanos[nanos.len() / 2]) / 2.0,
+            _ => nanos[nanos.len() / 2],
+        };
+        Self {
+            min: nanos[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Benchmark result of a single stage (`parse`, `part1` or `part2`) of a day.
+#[derive(Debug, Clone, PartialEq)]
+struct Measurement {
+    day: u8,
+    stage: String,
+    stats: Stats,
+}
+
+impl Measurement {
+    fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("day".to_string(), Json::Number(self.day as f64)),
+            ("stage".to_string(), Json::from(self.stage.as_str())),
+            ("min_ns".to_string(), Json::Number(self.stats.min.round())),
+            (
+                "median_ns".to_string(),
+                Json::Number(self.stats.median.round()),
+            ),
+            ("mean_ns".to_string(), Json::Number(self.stats.mean.round())),
+            (
+                "stddev_ns".to_string(),
+                Json::Number(self.stats.stddev.round()),
+            ),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Option<Self> {
+        let field = |key: &str| json.get(key).and_then(Json::as_f64);
+        Some(Self {
+            day: field("day")? as u8,
+            stage: json.get("stage")?.as_str()?.to_string(),
+            stats: Stats {
+                min: field("min_ns")?,
+                median: field("median_ns")?,
+                mean: field("mean_ns")?,
+                stddev: field("stddev_ns")?,
+            },
+        })
+    }
+}
+
+/// Benchmark the parser and the selected parts of every given day, printing
+/// the statistics and optionally comparing against and saving a baseline.
+///
+/// A day whose input cannot be read or parsed, or whose solver panics, is
+/// recorded in the summary and the other days are still measured.
+pub fn run(
+    puzzles: &[&dyn Puzzle],
+    source: &Source,
+    options: &BenchOptions,
+    summary: &mut Summary,
+) -> Result<(), String> {
+    let baseline = match &options.baseline {
+        Some(path) => load_baseline(path)?,
+        None => Vec::new(),
+    };
+
+    println!(
+        "{:>3}  {:<7}{:>11}{:>11}{:>11}{:>11}  Change",
+        "Day", "Stage", "Min", "Median", "Mean", "Stddev"
+    );
+    let mut results = Vec::new();
+    let (mut regressions, mut improvements) = (0, 0);
+    for puzzle in puzzles {
+        let input = match source.load(*puzzle) {
+            Ok(input) => input,
+            Err(error) => {
+                let failure = Failure::Error(error.to_string());
+                eprintln!("Day {}: {}", puzzle.day(), failure);
+                summary.add(None, puzzle.day(), Some(&failure));
+                continue;
+            }
+        };
+        let params = [source.params(*puzzle), &options.params].concat();
+        let stages = (1..=2)
+            .filter(|n| options.selection.part.includes(*n))
+            .map(Some);
+        let mut failure = None;
+        for part in [None].into_iter().chain(stages) {
+            let (warmup, samples) = (options.warmup, options.samples);
+            let samples =
+                match pool::catch(|| sample(*puzzle, part, &input, &params, warmup, samples)) {
+                    Ok(Ok(samples)) => samples,
+                    Ok(Err(error)) => {
+                        failure = Some(Failure::Error(error.to_string()));
+                        break;
+                    }
+                    Err(message) => {
+                        failure = Some(Failure::Panic(message));
+                        break;
+                    }
+                };
+            let measurement = Measurement {
+                day: puzzle.day(),
+                stage: part.map_or("parse".to_string(), |n| format!("part{}", n)),
+                stats: Stats::from_samples(&samples),
+            };
+
+            let previous = baseline
+                .iter()
+                .find(|base| base.day == measurement.day && base.stage == measurement.stage);
+            let change = match previous {
+                Some(previous) if previous.stats.median > 0.0 => {
+                    let change = (measurement.stats.median / previous.stats.median - 1.0) * 100.0;
+                    let label = if change > options.threshold {
+                        regressions += 1;
+                        " regressed"
+                    } else if change < -options.threshold {
+                        improvements += 1;
+                        " improved"
+                    } else {
+                        ""
+                    };
+                    format!("{:+.1}%{}", change, label)
+                }
+                _ => String::new(),
+            };
+
+            let stats = measurement.stats;
+            let nanos = |n: f64| format_duration(Some(Duration::from_nanos(n as u64)));
+            let row = format!(
+                "{:>3}  {:<7}{:>11}{:>11}{:>11}{:>11}  {}",
+                measurement.day,
+                measurement.stage,
+                nanos(stats.min),
+                nanos(stats.median),
+                nanos(stats.mean),
+                nanos(stats.stddev),
+                change
+            );
+            println!("{}", row.trim_end());
+            results.push(measurement);
+        }
+        if let Some(failure) = &failure {
+            eprintln!("Day {}: {}", puzzle.day(), failure);
+        }
+        summary.add(None, puzzle.day(), failure.as_ref());
+    }
+
+    if options.baseline.is_some() {
+        println!(
+            "\n{} regression(s) and {} improvement(s) beyond {}%.",
+            regressions, improvements, options.threshold
+        );
+    }
+
+    if let Some(path) = &options.save {
+        save_baseline(path, results)?;
+        println!("Saved baseline to '{}'.", path);
+    }
+    Ok(())
+}
+
+/// Measure the parser (`part` is `None`) or the given part of a day with the
+/// parameter overrides.
+///
+/// Parts consume their parsed input, so the input is parsed again outside of
+/// the timer for every repetition.
+fn sample(
+    puzzle: &dyn Puzzle,
+    part: Option<u8>,
+    input: &Reader,
+    params: &[(String, i64)],
+    warmup: usize,
+    samples: usize,
+) -> Result<Vec<Duration>, Error> {
+    let mut result = Vec::with_capacity(samples);
+    for i in 0..warmup + samples {
+        let reader = input.clone();
+        let elapsed = match part {
+            None => {
+                let start = Instant::now();
+                let parsed = puzzle.parse(reader)?;
+                let elapsed = start.elapsed();
+                drop(parsed);
+                elapsed
+            }
+            Some(n) => {
+                let parsed = puzzle.parse(reader)?;
+                let start = Instant::now();
+                let answer = puzzle.solve_with(n, parsed, params);
+                let elapsed = start.elapsed();
+                drop(answer);
+                elapsed
+            }
+        };
+        if i >= warmup {
+            result.push(elapsed);
+        }
+    }
+    Ok(result)
+}
+
+fn load_baseline(path: &str) -> Result<Vec<Measurement>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| format!("Unable to read baseline '{}': {}", path, error))?;
+    let json = text.parse::<Json>()?;
+    json.get("results")
+        .and_then(Json::as_array)
+        .ok_or_else(|| format!("Baseline '{}' has no \"results\" array.", path))?
+        .iter()
+        .map(|entry| {
+            Measurement::from_json(entry)
+                .ok_or_else(|| format!("Invalid baseline entry in '{}': {}", path, entry))
+        })
+        .collect()
+}
+
+/// Save the results, keeping entries of an existing file that were not measured again.
+fn save_baseline(path: &str, results: Vec<Measurement>) -> Result<(), String> {
+    let mut merged = match std::path::Path::new(path).exists() {
+        true => load_baseline(path)?,
+        false => Vec::new(),
+    };
+    merged.retain(|old| {
+        !results
+            .iter()
+            .any(|new| new.day == old.day && new.stage == old.stage)
+    });
+    merged.extend(results);
+    merged.sort_by(|a, b| (a.day, &a.stage).cmp(&(b.day, &b.stage)));
+
+    let json = Json::Object(vec![(
+        "results".to_string(),
+        Json::Array(merged.iter().map(Measurement::to_json).collect()),
+    )]);
+    std::fs::write(path, json.pretty() + "\n")
+        .map_err(|error| format!("Unable to write baseline '{}': {}", path, error))
+}
+
+#[test]
+fn test_stats() {
+    let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+    let stats = Stats::from_samples(&samples);
+    assert_eq!(stats.min, 1.0);
+    assert_eq!(stats.median, 2.5);
+    assert_eq!(stats.mean, 2.5);
+    assert!((stats.stddev - 1.25f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn test_measurement_json() {
+    let measurement = Measurement {
+        day: 16,
+        stage: "part2".to_string(),
+        stats: Stats {
+            min: 1.0,
+            median: 2.0,
+            mean: 3.0,
+            stddev: 4.0,
+        },
+    };
+    assert_eq!(
+        Measurement::from_json(&measurement.to_json()),
+        Some(measurement)
+    );
+}
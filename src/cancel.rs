@@ -0,0 +1,96 @@
+//! Cooperative cancellation of solvers. A part that exceeds `--timeout` is
+//! reported as timed out, and solvers with long running loops call `check`
+//! in them so they stop as soon as their budget is spent.
+
+use std::cell::RefCell;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// Token of the solver running on this thread, if it can be cancelled.
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Shared flag telling a solver to stop, set explicitly or once its deadline
+/// has passed.
+#[derive(Debug, Clone, Default)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl Token {
+    /// A token that is cancelled once the timeout has elapsed.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Instant::now().checked_add(timeout),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .map_or(false, |deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Payload of the unwind started by `check`.
+#[derive(Debug)]
+pub struct Cancelled;
+
+/// Run `f` with the token as the token of the current thread, so `check`
+/// calls inside `f` observe it.
+pub fn scope<R>(token: Token, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(token)));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Whether the token of the current thread was cancelled, for solvers that
+/// prefer to return early themselves.
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().as_ref().map_or(false, Token::is_cancelled))
+}
+
+/// Stop the current solver if its token was cancelled. Call it in long
+/// running loops, it does nothing when there is no token.
+///
+/// Unwinds with a `Cancelled` payload, without invoking the panic hook.
+pub fn check() {
+    if is_cancelled() {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+/// Restores the previous token of the thread, even when `f` unwinds.
+struct Restore(Option<Token>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+#[test]
+fn test_check() {
+    check();
+    assert!(!is_cancelled());
+
+    let token = Token::default();
+    scope(token.clone(), check);
+    token.cancel();
+    let result = panic::catch_unwind(|| scope(token, check));
+    assert!(result.unwrap_err().is::<Cancelled>());
+    assert!(!is_cancelled());
+
+    assert!(Token::with_timeout(Duration::ZERO).is_cancelled());
+    assert!(!Token::with_timeout(Duration::from_secs(60)).is_cancelled());
+}
@@ -0,0 +1,745 @@
+use crate::report::Format;
+use crate::solution::Puzzle;
+use std::time::Duration;
+
+/// Usage text printed by `help` and on invalid arguments.
+pub const USAGE: &str = "\
+Usage: advent_of_code_2022 [COMMAND] [DAYS] [OPTIONS]
+
+Commands:
+    run          Run the selected days (default)
+    bench        Benchmark the parser and solvers of the selected days
+    verify       Compare the answers of the selected days with the answers file
+    watch <N>    Re-run the tests and solution of a day when its source or inputs change
+    new-day <N>  Create and register the module and input files of a new day
+    generate <N> Print a random input of a day in the format of the puzzle input
+    help         Print this message
+
+Days:
+    all         Every registered day (default)
+    N           A single day, e.g. `16`
+    N-M         An inclusive range, e.g. `1-10`
+    A,B,...     A comma separated list of the above, e.g. `1-5,16`
+
+Options:
+    -p, --part <1|2>    Only run the given part
+    --input-dir <DIR>   Input root (default $AOC_INPUT_DIR, otherwise files)
+    --profile <NAME>    Use the inputs in a subdirectory of the input root,
+                        `all` for the root and every subdirectory with inputs
+
+Run options:
+    -t, --time          Print a table of parse and solve times instead of answers
+    --no-io             Read every input before timing so file I/O is excluded
+    -i, --input <PATH>  Read the input of a single day from a file, `-` for stdin
+    -e, --example [N]   Solve example N (default 1) of files/dayN_test*.txt and compare
+                        the answers with files/examples.toml, or examplesN.toml.
+                        Before the days, N is given as --example=N
+    --format <FORMAT>   Print one record per part as `json` or `csv` instead of text
+    -j, --jobs <N>      Solve the days and parts on N threads, printing in day order
+    --timeout <SECS>    Give up on a part that takes longer than SECS seconds
+    --param <NAME=N>    Override a parameter of the solvers, see the list below
+
+Bench options:
+    -n, --samples <N>   Number of measured repetitions (default 10)
+    -w, --warmup <N>    Number of unmeasured repetitions (default 1)
+    --save <PATH>       Save the results as a JSON baseline
+    --baseline <PATH>   Compare the results against a saved baseline
+    --threshold <PCT>   Median change reported as a regression (default 5)
+    --param <NAME=N>    Override a parameter of the solvers, as for run
+
+Verify options:
+    --answers <PATH>    Answers file (default answers.toml in the input directory)
+    --record            Store the answers of parts that have no entry yet
+
+Watch options:
+    --interval <SECS>   Time between checks for changes (default 0.5)
+
+Generate options:
+    --seed <N>          Seed of the random input, the same seed gives the same input (default 0)
+    --size <N>          Number of lines, items or the width of the map depending on the day
+                        (default the size of a real input)
+
+Exit status:
+    0   Every day succeeded
+    1   A day could not be read or parsed, or an answer did not match
+    2   Invalid arguments
+    3   A day panicked
+    4   A day timed out";
+
+/// Puzzle part selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    /// Whether the part with the given number (`1` or `2`) is selected.
+    pub fn includes(&self, n: u8) -> bool {
+        matches!((self, n), (Self::Both, _) | (Self::One, 1) | (Self::Two, 2))
+    }
+}
+
+/// Days, parts and input profiles selected on the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Selection {
+    pub days: Vec<u8>,
+    pub part: Part,
+    pub input_dir: Option<String>,
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub time: bool,
+    pub no_io: bool,
+    /// Input used instead of the input file, `-` for stdin.
+    pub input: Option<String>,
+    /// Number of the example input used instead of the input file.
+    pub example: Option<u8>,
+    /// Machine-readable output format, `None` for text.
+    pub format: Option<Format>,
+    /// Number of threads, `None` to solve the days one by one as they are printed.
+    pub jobs: Option<usize>,
+    /// Time after which a part is given up on and reported as timed out.
+    pub timeout: Option<Duration>,
+    /// Parameter overrides in the given order, see `solution::Param`.
+    pub params: Vec<(String, i64)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub selection: Selection,
+    pub samples: usize,
+    pub warmup: usize,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    pub threshold: f64,
+    /// Parameter overrides by name, see `RunOptions::params`.
+    pub params: Vec<(String, i64)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyOptions {
+    pub selection: Selection,
+    /// Answers file used instead of the one in the input directory.
+    pub answers: Option<String>,
+    pub record: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Selection of a single day.
+    pub selection: Selection,
+    /// Time between checks for changes.
+    pub interval: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenerateOptions {
+    pub day: u8,
+    pub seed: u64,
+    /// Size of the input, `None` for the size of a real input.
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    Watch(WatchOptions),
+    NewDay(u8),
+    Generate(GenerateOptions),
+    Help,
+}
+
+/// Remaining command-line arguments.
+struct Args {
+    iter: std::vec::IntoIter<String>,
+    /// Whether the days were already given, a number after that cannot be
+    /// the days anymore.
+    days: bool,
+}
+
+impl Args {
+    /// Take the value following the given flag.
+    fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.iter
+            .next()
+            .ok_or_else(|| format!("Missing value for '{}'.", flag))
+    }
+
+    /// Take the value following the given flag and parse it into the given type.
+    fn parsed<T: std::str::FromStr>(&mut self, flag: &str) -> Result<T, String> {
+        parse_value(flag, &self.value(flag)?)
+    }
+
+    /// Take the next argument if it parses into the given type, for flags
+    /// whose value is optional. Before the days are given the next argument is
+    /// left for them, e.g. `--example 9` selects day 9.
+    fn optional<T: std::str::FromStr>(&mut self) -> Option<T> {
+        if !self.days {
+            return None;
+        }
+        let value = self.iter.as_slice().first()?.parse().ok()?;
+        self.iter.next();
+        Some(value)
+    }
+}
+
+/// Parse the command-line arguments, excluding the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter().collect::<Vec<String>>();
+    // `help` is only a command in first position, other arguments may be
+    // named so, e.g. an input file.
+    let help = args.first().map(String::as_str) == Some("help");
+    if help
+        || args
+            .iter()
+            .any(|arg| matches!(arg.as_str(), "-h" | "--help"))
+    {
+        return Ok(Command::Help);
+    }
+
+    let command = match args.first().map(|arg| arg.as_str()) {
+        Some(command @ ("run" | "bench" | "verify" | "watch" | "new-day" | "generate")) => {
+            command.to_string()
+        }
+        _ => "run".to_string(),
+    };
+    if args.first() == Some(&command) {
+        args.remove(0);
+    }
+
+    match command.as_str() {
+        "bench" => parse_bench(args),
+        "verify" => parse_verify(args),
+        "watch" => parse_watch(args),
+        "generate" => parse_generate(args),
+        "new-day" => match args.as_slice() {
+            [day] => Ok(Command::NewDay(parse_day(day)?)),
+            _ => Err("Expected a single day number for 'new-day'.".to_string()),
+        },
+        _ => parse_run(args),
+    }
+}
+
+fn parse_run(args: Vec<String>) -> Result<Command, String> {
+    let mut time = false;
+    let mut no_io = false;
+    let mut input = None;
+    let mut example = None;
+    let mut format = None;
+    let mut jobs = None;
+    let mut timeout = None;
+    let mut params = Vec::new();
+    let selection = parse_selection(args, |flag, args| {
+        match flag {
+            "-t" | "--time" => time = true,
+            "--no-io" => no_io = true,
+            "-i" | "--input" => input = Some(args.value(flag)?),
+            "-e" | "--example" => example = Some(args.optional().unwrap_or(1)),
+            flag if flag.starts_with("--example=") => {
+                example = Some(parse_value("--example", &flag["--example=".len()..])?)
+            }
+            "-j" | "--jobs" => jobs = Some(args.parsed(flag)?),
+            "--timeout" => timeout = Some(parse_seconds(flag, &args.value(flag)?)?),
+            "--param" => params.push(parse_param(&args.value(flag)?)?),
+            "--format" => {
+                format = match args.value(flag)?.as_str() {
+                    "text" => None,
+                    value => Some(value.parse()?),
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    if input.is_some() && selection.days.len() != 1 {
+        return Err("'--input' requires a single day.".to_string());
+    }
+    if input.is_some() && example.is_some() {
+        return Err("'--input' cannot be combined with '--example'.".to_string());
+    }
+    if example == Some(0) {
+        return Err("Examples are numbered from 1.".to_string());
+    }
+    if time && format.is_some() {
+        return Err("'--time' cannot be combined with '--format'.".to_string());
+    }
+    if time && jobs.is_some() {
+        return Err("'--time' cannot be combined with '--jobs'.".to_string());
+    }
+    if time && timeout.is_some() {
+        return Err("'--time' cannot be combined with '--timeout'.".to_string());
+    }
+    if jobs == Some(0) {
+        return Err("At least one job is required.".to_string());
+    }
+
+    Ok(Command::Run(RunOptions {
+        selection,
+        time,
+        no_io,
+        input,
+        example,
+        format,
+        jobs,
+        timeout,
+        params,
+    }))
+}
+
+fn parse_bench(args: Vec<String>) -> Result<Command, String> {
+    let mut samples = 10;
+    let mut warmup = 1;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 5.0;
+    let mut params = Vec::new();
+    let selection = parse_selection(args, |flag, args| {
+        match flag {
+            "-n" | "--samples" => samples = args.parsed(flag)?,
+            "-w" | "--warmup" => warmup = args.parsed(flag)?,
+            "--save" => save = Some(args.value(flag)?),
+            "--baseline" => baseline = Some(args.value(flag)?),
+            "--threshold" => threshold = args.parsed(flag)?,
+            "--param" => params.push(parse_param(&args.value(flag)?)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    if samples == 0 {
+        return Err("At least one sample is required.".to_string());
+    }
+
+    Ok(Command::Bench(BenchOptions {
+        selection,
+        samples,
+        warmup,
+        save,
+        baseline,
+        threshold,
+        params,
+    }))
+}
+
+fn parse_verify(args: Vec<String>) -> Result<Command, String> {
+    let mut answers = None;
+    let mut record = false;
+    let selection = parse_selection(args, |flag, args| {
+        match flag {
+            "--answers" => answers = Some(args.value(flag)?),
+            "--record" => record = true,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    if answers.is_some() && selection.profile.as_deref() == Some("all") {
+        return Err("'--answers' cannot be used with every profile.".to_string());
+    }
+
+    Ok(Command::Verify(VerifyOptions {
+        selection,
+        answers,
+        record,
+    }))
+}
+
+fn parse_watch(args: Vec<String>) -> Result<Command, String> {
+    let mut interval = Duration::from_millis(500);
+    let selection = parse_selection(args, |flag, args| {
+        match flag {
+            "--interval" => interval = parse_seconds(flag, &args.value(flag)?)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    if selection.days.len() != 1 {
+        return Err("Expected a single day for 'watch'.".to_string());
+    }
+
+    Ok(Command::Watch(WatchOptions {
+        selection,
+        interval,
+    }))
+}
+
+fn parse_generate(args: Vec<String>) -> Result<Command, String> {
+    let mut seed = 0;
+    let mut size = None;
+    let selection = parse_selection(args, |flag, args| {
+        match flag {
+            "--seed" => seed = args.parsed(flag)?,
+            "--size" => size = Some(args.parsed(flag)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    if selection.days.len() != 1 {
+        return Err("Expected a single day for 'generate'.".to_string());
+    }
+
+    Ok(Command::Generate(GenerateOptions {
+        day: selection.days[0],
+        seed,
+        size,
+    }))
+}
+
+/// Parse the day selection and part shared by every command.
+///
+/// Command specific flags are passed to `option`, which returns `Ok(false)`
+/// for flags it does not recognize.
+fn parse_selection(
+    args: Vec<String>,
+    mut option: impl FnMut(&str, &mut Args) -> Result<bool, String>,
+) -> Result<Selection, String> {
+    let mut args = Args {
+        iter: args.into_iter(),
+        days: false,
+    };
+    let mut days = None;
+    let mut part = Part::Both;
+    let mut input_dir = None;
+    let mut profile = None;
+    while let Some(arg) = args.iter.next() {
+        match arg.as_str() {
+            "-p" | "--part" => part = parse_part(&args.value(&arg)?)?,
+            "--input-dir" => input_dir = Some(args.value(&arg)?),
+            "--profile" => profile = Some(args.value(&arg)?),
+            flag if flag.starts_with('-') => {
+                if !option(flag, &mut args)? {
+                    return Err(format!("Unknown option '{}'.", flag));
+                }
+            }
+            _ if days.is_some() => return Err(format!("Unexpected argument '{}'.", arg)),
+            _ => {
+                days = Some(parse_days(&arg)?);
+                args.days = true;
+            }
+        }
+    }
+
+    Ok(Selection {
+        days: days.unwrap_or_else(|| (1..=25).collect()),
+        part,
+        input_dir,
+        profile,
+    })
+}
+
+/// Parse the value of a flag into the given type.
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{}' for '{}'.", value, flag))
+}
+
+/// Parse the value of a flag as a positive number of seconds, e.g. `10` or `0.5`.
+fn parse_seconds(flag: &str, str: &str) -> Result<Duration, String> {
+    match str.parse().map(Duration::try_from_secs_f64) {
+        Ok(Ok(duration)) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!(
+            "Invalid value '{}' for '{}', expected a positive number of seconds.",
+            str, flag
+        )),
+    }
+}
+
+/// Parse a parameter override such as `rounds=500`.
+fn parse_param(str: &str) -> Result<(String, i64), String> {
+    let invalid = || format!("Invalid parameter '{}', expected NAME=N.", str);
+    let (name, value) = str.split_once('=').ok_or_else(invalid)?;
+    let value = value
+        .trim()
+        .replace('_', "")
+        .parse()
+        .map_err(|_| invalid())?;
+    match name.trim() {
+        "" => Err(invalid()),
+        name => Ok((name.to_string(), value)),
+    }
+}
+
+/// Check that every overridden parameter is declared by one of the days, with
+/// a value in the range of every part declaring it.
+pub fn check_params(puzzles: &[&dyn Puzzle], params: &[(String, i64)]) -> Result<(), String> {
+    for (name, value) in params {
+        let mut declared = puzzles
+            .iter()
+            .flat_map(|puzzle| puzzle.params().iter().map(|param| (puzzle.day(), param)))
+            .filter(|(_, param)| param.name == name)
+            .peekable();
+        if declared.peek().is_none() {
+            return Err(format!(
+                "None of the selected days has a parameter '{}'.",
+                name
+            ));
+        }
+        let invalid = declared.find(|(_, param)| !(param.min..=param.max).contains(value));
+        if let Some((day, param)) = invalid {
+            let expected = match (param.min, param.max) {
+                (min, i64::MAX) => format!("at least {}", min),
+                (i64::MIN, max) => format!("at most {}", max),
+                (min, max) => format!("from {} to {}", min, max),
+            };
+            return Err(format!(
+                "Invalid value {} for '{}' of day {} part {}, expected {}.",
+                value, name, day, param.part, expected
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// List the parameters declared by the given days with their defaults.
+pub fn params_usage(puzzles: &[&dyn Puzzle]) -> String {
+    let mut lines = vec!["Parameters:".to_string()];
+    for puzzle in puzzles {
+        let params = puzzle.params();
+        for (i, param) in params.iter().enumerate() {
+            if params[..i].iter().any(|other| other.name == param.name) {
+                continue;
+            }
+            let defaults = params
+                .iter()
+                .filter(|other| other.name == param.name)
+                .map(|other| format!("part {}: {}", other.part, other.default))
+                .collect::<Vec<String>>()
+                .join(", ");
+            lines.push(format!(
+                "    day {:<3} {:<10}{} ({})",
+                puzzle.day(),
+                param.name,
+                param.description,
+                defaults
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+fn parse_part(str: &str) -> Result<Part, String> {
+    match str {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("Invalid part '{}', expected 1 or 2.", str)),
+    }
+}
+
+/// Parse a day selection such as `all`, `16`, `1-10` or `1-5,16` into a
+/// sorted list of unique day numbers.
+pub fn parse_days(str: &str) -> Result<Vec<u8>, String> {
+    if str == "all" {
+        return Ok((1..=25).collect());
+    }
+
+    let mut days = Vec::new();
+    for part in str.split(',') {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+            None => (parse_day(part)?, parse_day(part)?),
+        };
+        if start > end {
+            return Err(format!("Invalid day range '{}'.", part));
+        }
+        days.extend(start..=end);
+    }
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+fn parse_day(str: &str) -> Result<u8, String> {
+    match str.trim().parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!(
+            "Invalid day '{}', expected a number from 1 to 25.",
+            str
+        )),
+    }
+}
+
+#[test]
+fn test_parse_days() {
+    assert_eq!(parse_days("16"), Ok(vec![16]));
+    assert_eq!(parse_days("3-5,1,4"), Ok(vec![1, 3, 4, 5]));
+    assert_eq!(parse_days("all").unwrap().len(), 25);
+    assert!(parse_days("5-3").is_err());
+    assert!(parse_days("26").is_err());
+}
+
+#[test]
+fn test_parse() {
+    let args = ["run", "16", "--part", "2"].map(String::from);
+    assert_eq!(
+        parse(args),
+        Ok(Command::Run(RunOptions {
+            selection: Selection {
+                days: vec![16],
+                part: Part::Two,
+                input_dir: None,
+                profile: None,
+            },
+            time: false,
+            no_io: false,
+            input: None,
+            example: None,
+            format: None,
+            jobs: None,
+            timeout: None,
+            params: vec![],
+        }))
+    );
+    match parse(["--format", "csv"].map(String::from)) {
+        Ok(Command::Run(options)) => assert_eq!(options.format, Some(Format::Csv)),
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["--format", "xml"].map(String::from)).is_err());
+    assert!(parse(["--format", "json", "--time"].map(String::from)).is_err());
+    assert!(parse(["--jobs", "0"].map(String::from)).is_err());
+    match parse(["--timeout", "2.5"].map(String::from)) {
+        Ok(Command::Run(options)) => {
+            assert_eq!(options.timeout, Some(Duration::from_millis(2500)))
+        }
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["--timeout", "0"].map(String::from)).is_err());
+    assert!(parse(["--timeout", "-1"].map(String::from)).is_err());
+    assert!(parse(["--timeout", "5", "--time"].map(String::from)).is_err());
+    match parse(["11", "--param", "rounds=1_000", "--param", "x=-2"].map(String::from)) {
+        Ok(Command::Run(options)) => assert_eq!(
+            options.params,
+            vec![("rounds".to_string(), 1000), ("x".to_string(), -2)]
+        ),
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["--param", "rounds"].map(String::from)).is_err());
+    assert!(parse(["--param", "=5"].map(String::from)).is_err());
+    assert!(parse(["--param", "rounds=many"].map(String::from)).is_err());
+    assert!(parse(["--input", "-"].map(String::from)).is_err());
+    match parse(["run", "5", "-i", "-"].map(String::from)) {
+        Ok(Command::Run(options)) => assert_eq!(options.input, Some("-".to_string())),
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    match parse(["run", "9", "--example", "2"].map(String::from)) {
+        Ok(Command::Run(options)) => assert_eq!(options.example, Some(2)),
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    match parse(["run", "--example", "9"].map(String::from)) {
+        Ok(Command::Run(options)) => {
+            assert_eq!(options.example, Some(1));
+            assert_eq!(options.selection.days, vec![9]);
+        }
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    match parse(["run", "--example=2", "9"].map(String::from)) {
+        Ok(Command::Run(options)) => {
+            assert_eq!(options.example, Some(2));
+            assert_eq!(options.selection.days, vec![9]);
+        }
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["run", "9", "--example=x"].map(String::from)).is_err());
+    match parse(["run", "--example", "-p", "1"].map(String::from)) {
+        Ok(Command::Run(options)) => assert_eq!(options.example, Some(1)),
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["run", "5", "-e", "-i", "-"].map(String::from)).is_err());
+    assert_eq!(parse(["help"].map(String::from)), Ok(Command::Help));
+    assert_eq!(parse(["5", "-h"].map(String::from)), Ok(Command::Help));
+    match parse(["5", "--input", "help"].map(String::from)) {
+        Ok(Command::Run(options)) => assert_eq!(options.input, Some("help".to_string())),
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["--part", "3"].map(String::from)).is_err());
+    assert!(parse(["16", "--samples", "3"].map(String::from)).is_err());
+
+    let args = [
+        "bench",
+        "1-2",
+        "--samples",
+        "3",
+        "--save",
+        "base.json",
+        "--param",
+        "row=5",
+    ];
+    match parse(args.map(String::from)) {
+        Ok(Command::Bench(options)) => {
+            assert_eq!(options.selection.days, vec![1, 2]);
+            assert_eq!(options.samples, 3);
+            assert_eq!(options.save, Some("base.json".to_string()));
+            assert_eq!(options.params, vec![("row".to_string(), 5)]);
+        }
+        command => panic!("Unexpected command: {:?}", command),
+    }
+
+    assert_eq!(
+        parse(["new-day", "12"].map(String::from)),
+        Ok(Command::NewDay(12))
+    );
+    assert!(parse(["new-day", "1-2"].map(String::from)).is_err());
+
+    match parse(["watch", "12", "--interval", "2"].map(String::from)) {
+        Ok(Command::Watch(options)) => {
+            assert_eq!(options.selection.days, vec![12]);
+            assert_eq!(options.interval, Duration::from_secs(2));
+        }
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(parse(["watch"].map(String::from)).is_err());
+
+    assert_eq!(
+        parse(["generate", "16", "--seed", "42", "--size", "10"].map(String::from)),
+        Ok(Command::Generate(GenerateOptions {
+            day: 16,
+            seed: 42,
+            size: Some(10),
+        }))
+    );
+    assert!(parse(["generate", "1-2"].map(String::from)).is_err());
+    assert!(parse(["generate", "3", "--seed", "-1"].map(String::from)).is_err());
+    assert!(parse(["watch", "1-2"].map(String::from)).is_err());
+}
+
+#[test]
+fn test_parse_profiles() {
+    let args = ["verify", "--input-dir", "inputs", "--profile", "all"].map(String::from);
+    match parse(args) {
+        Ok(Command::Verify(options)) => {
+            assert_eq!(options.selection.input_dir, Some("inputs".to_string()));
+            assert_eq!(options.selection.profile, Some("all".to_string()));
+        }
+        command => panic!("Unexpected command: {:?}", command),
+    }
+    assert!(
+        parse(["verify", "--profile", "all", "--answers", "a.toml"].map(String::from)).is_err()
+    );
+}
+
+#[test]
+fn test_check_params() {
+    let puzzles = [11, 20].map(|day| crate::days::get(day).unwrap());
+    let params = |name: &str, value| [(name.to_string(), value)];
+    assert!(check_params(&puzzles, &params("rounds", 50)).is_ok());
+    assert!(check_params(&puzzles, &params("minutes", 10)).is_err());
+    assert_eq!(
+        check_params(&puzzles, &params("rounds", -1)),
+        Err("Invalid value -1 for 'rounds' of day 11 part 1, expected at least 0.".to_string())
+    );
+    assert_eq!(
+        check_params(&puzzles, &params("key", 1 << 50))
+            .map_err(|error| error.ends_with("expected from -1000000000000 to 1000000000000.")),
+        Err(true)
+    );
+}
@@ -0,0 +1,219 @@
+//! Cycle detection for simulations run for more steps than is practical, such
+//! as dropping 10^12 rocks on day 17. `hashed` steps a state until its
+//! fingerprint repeats and extrapolates a metric from the cycle, `floyd` and
+//! `brent` find cycles while keeping only two states.
+
+use crate::cancel;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cycle in a sequence of states, the state after `start + length` steps
+/// has the same fingerprint as the state after `start` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as `step`, and the number of
+    /// whole cycles between them.
+    pub fn reduce(&self, step: usize) -> (usize, usize) {
+        match step.checked_sub(self.start) {
+            Some(offset) => (self.start + offset % self.length, offset / self.length),
+            None => (step, 0),
+        }
+    }
+
+    /// Value of a metric after `step` steps, assuming it changes by the same
+    /// amount in every cycle. `metrics[n]` is its value after `n` steps, it
+    /// must cover at least the first cycle.
+    pub fn extrapolate(&self, metrics: &[i64], step: usize) -> i64 {
+        let (reduced, cycles) = self.reduce(step);
+        let gain = metrics[self.start + self.length] - metrics[self.start];
+        metrics[reduced] + gain * cycles as i64
+    }
+}
+
+/// Floyd's tortoise and hare, for sequences that are known to cycle.
+///
+/// Keeps only two states, compared by their fingerprint, but computes the
+/// states before the cycle three times.
+pub fn floyd<S, K: PartialEq>(
+    initial: S,
+    mut next: impl FnMut(&S) -> S,
+    mut fingerprint: impl FnMut(&S) -> K,
+) -> Cycle {
+    let mut tortoise = next(&initial);
+    let mut hare = next(&tortoise);
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        cancel::check();
+        tortoise = next(&tortoise);
+        let halfway = next(&hare);
+        hare = next(&halfway);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        cancel::check();
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = next(&tortoise);
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        cancel::check();
+        hare = next(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, for sequences that are known to cycle. Like `floyd`
+/// it keeps only two states, it needs fewer steps to find the length.
+pub fn brent<S: Clone, K: PartialEq>(
+    initial: S,
+    mut next: impl FnMut(&S) -> S,
+    mut fingerprint: impl FnMut(&S) -> K,
+) -> Cycle {
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = next(&initial);
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        cancel::check();
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        cancel::check();
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Steps of a simulation and the values of its metric, see `hashed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    /// The first cycle, if one was found within the limit.
+    pub cycle: Option<Cycle>,
+    /// Value of the metric after every step taken, starting with the
+    /// initial state.
+    pub metrics: Vec<i64>,
+}
+
+impl History {
+    /// Value of the metric after `step` steps, extrapolated over the cycle
+    /// when there is one. `None` if the simulation stopped before without
+    /// finding a cycle.
+    pub fn at(&self, step: usize) -> Option<i64> {
+        match self.cycle {
+            Some(cycle) => Some(cycle.extrapolate(&self.metrics, step)),
+            None => self.metrics.get(step).copied(),
+        }
+    }
+}
+
+/// Step the state in place until the fingerprint of a state repeats one of
+/// an earlier state, or `limit` steps were taken, recording the `metric`
+/// after every step.
+///
+/// Remembers every fingerprint, for simulations that are expensive to clone
+/// or run more than once, or that may never cycle.
+pub fn hashed<S, K: Eq + Hash>(
+    mut state: S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut fingerprint: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> i64,
+) -> History {
+    let mut seen = HashMap::from([(fingerprint(&state), 0)]);
+    let mut metrics = vec![metric(&state)];
+    for steps in 1..=limit {
+        cancel::check();
+        step(&mut state);
+        metrics.push(metric(&state));
+        match seen.entry(fingerprint(&state)) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                let cycle = Cycle {
+                    start,
+                    length: steps - start,
+                };
+                return History {
+                    cycle: Some(cycle),
+                    metrics,
+                };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(steps);
+            }
+        }
+    }
+    History {
+        cycle: None,
+        metrics,
+    }
+}
+
+#[cfg(test)]
+fn next_value(n: &u64) -> u64 {
+    // 0, 1, 2, 5, 3, 10, 9, 13, 9, 13, ... cycles from step 6 with length 2.
+    (n * n + 1) % 23
+}
+
+#[test]
+fn test_floyd_and_brent() {
+    let expected = Cycle {
+        start: 6,
+        length: 2,
+    };
+    assert_eq!(floyd(0, next_value, |n| *n), expected);
+    assert_eq!(brent(0, next_value, |n| *n), expected);
+    assert_eq!(expected.reduce(4), (4, 0));
+    assert_eq!(expected.reduce(13), (7, 3));
+}
+
+#[test]
+fn test_hashed() {
+    let step = |n: &mut u64| *n = next_value(n);
+    let history = hashed(0, 100, step, |n| *n, |n| *n as i64);
+    assert_eq!(
+        history.cycle,
+        Some(Cycle {
+            start: 6,
+            length: 2
+        })
+    );
+    assert_eq!(history.at(5), Some(10));
+    assert_eq!(history.at(1_000), Some(9));
+    assert_eq!(history.at(1_001), Some(13));
+
+    // A counter never repeats.
+    let history = hashed(0, 10, |n: &mut u64| *n += 1, |n| *n, |n| *n as i64);
+    assert_eq!(
+        (history.cycle, history.at(10), history.at(11)),
+        (None, Some(10), None)
+    );
+
+    // Its fingerprint modulo 4 does, the metric keeps growing every cycle.
+    let growing = hashed(0, 10, |n: &mut u64| *n += 1, |n| *n % 4, |n| *n as i64);
+    assert_eq!(growing.at(1_000), Some(1_000));
+}
@@ -1,39 +1,49 @@
 use std::collections::BinaryHeap;
 
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 
-pub fn run() {
-    println!(
-        "Day 1\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<usize>>;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        let groups = input
+            .blocks()
+            .into_iter()
+            .map(|group| group.try_lines_as_all());
+        Ok(groups.collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
 
-fn input() -> Vec<String> {
-    reader::open("files/day1.txt").split_on_empty_line()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(groups: Vec<String>) -> usize {
+pub fn part_one(groups: Vec<Vec<usize>>) -> usize {
     sum_descending_calories(groups, 1)
 }
 
-fn part_two(groups: Vec<String>) -> usize {
+pub fn part_two(groups: Vec<Vec<usize>>) -> usize {
     sum_descending_calories(groups, 3)
 }
 
-fn sum_descending_calories(groups: Vec<String>, n: usize) -> usize {
+fn sum_descending_calories(groups: Vec<Vec<usize>>, n: usize) -> usize {
     get_descending_calories(groups).into_iter().take(n).sum()
 }
 
-fn get_descending_calories(groups: Vec<String>) -> BinaryHeap<usize> {
+fn get_descending_calories(groups: Vec<Vec<usize>>) -> BinaryHeap<usize> {
     groups
         .iter()
-        .map(|part| {
-            part.lines()
-                .map(|line| line.parse::<usize>().unwrap())
-                .sum()
-        })
+        .map(|group| group.iter().sum())
         .collect::<BinaryHeap<usize>>()
 }
 
@@ -48,6 +58,6 @@ fn test_part_two() {
 }
 
 #[cfg(test)]
-fn get_test_input() -> Vec<String> {
-    reader::open("files/day1_test.txt").split_on_empty_line()
+fn get_test_input() -> Vec<Vec<usize>> {
+    Day1::parse(reader::open("files/day1_test.txt")).unwrap()
 }
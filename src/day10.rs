@@ -1,35 +1,46 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::str::FromStr;
-use std::string::ParseError;
 
-enum Instruction {
+pub enum Instruction {
     Noop,
     Add(i32),
 }
 
 impl FromStr for Instruction {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         Ok(match str.split_once(' ') {
-            Some((_, value)) => Instruction::Add(value.parse().unwrap()),
+            Some((_, value)) => {
+                Instruction::Add(value.parse().map_err(|error| format!("{}", error))?)
+            }
             None => Instruction::Noop,
         })
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 10\n\tPart 1: {:?}\n\tPart 2:\n{}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day10;
 
-fn input() -> Vec<Instruction> {
-    reader::open("files/day10.txt").lines_as()
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Answer::Image(part_two(input).lines().map(String::from).collect())
+    }
 }
 
-fn part_one(values: Vec<Instruction>) -> i32 {
+pub fn part_one(values: Vec<Instruction>) -> i32 {
     execute_instructions(values)
         .into_iter()
         .enumerate()
@@ -39,7 +50,7 @@ fn part_one(values: Vec<Instruction>) -> i32 {
         .sum()
 }
 
-fn part_two(values: Vec<Instruction>) -> String {
+pub fn part_two(values: Vec<Instruction>) -> String {
     execute_instructions(values)
         .into_iter()
         .enumerate()
@@ -1,6 +1,9 @@
+use crate::cycle;
+use crate::error::Error;
+use crate::pattern;
 use crate::reader;
-use std::str::{FromStr, Lines};
-use std::string::ParseError;
+use crate::solution::{Answer, Param, Params, Solution};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Operation {
@@ -9,14 +12,22 @@ struct Operation {
     operator: char,
 }
 
-impl From<&str> for Operation {
-    fn from(str: &str) -> Self {
-        let mut iter = str[19..].split(' ');
-        Self {
-            lhs: iter.next().unwrap().parse().unwrap_or(0),
-            operator: iter.next().unwrap().chars().next().unwrap(),
-            rhs: iter.next().unwrap().parse().unwrap_or(0),
-        }
+/// Parses the right hand side of `new = old * 19`, `old` is stored as 0.
+impl FromStr for Operation {
+    type Err = String;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (lhs, operator, rhs): (String, char, String) = pattern::parse("{} {} {}", str)?;
+        let operand = |value: String| match value.as_str() {
+            "old" => Ok(0),
+            _ => value
+                .parse()
+                .map_err(|error| format!("operand {:?}: {}", value, error)),
+        };
+        Ok(Self {
+            lhs: operand(lhs)?,
+            rhs: operand(rhs)?,
+            operator,
+        })
     }
 }
 
@@ -39,17 +50,6 @@ struct Test {
     on_false: usize,
 }
 
-impl From<Lines<'_>> for Test {
-    fn from(str: Lines<'_>) -> Self {
-        let mut iter = str;
-        Self {
-            diviser: iter.next().unwrap()[21..].parse().unwrap(),
-            on_true: iter.next().unwrap()[29..].parse().unwrap(),
-            on_false: iter.next().unwrap()[30..].parse().unwrap(),
-        }
-    }
-}
-
 impl Test {
     fn execute(&self, value: usize) -> usize {
         if value % self.diviser == 0 {
@@ -61,7 +61,7 @@ impl Test {
 }
 
 #[derive(Debug)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<usize>,
     operation: Operation,
     test: Test,
@@ -69,16 +69,32 @@ struct Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let mut iter = str.lines();
-        iter.next().unwrap();
-        let items = iter.next().unwrap()[18..]
-            .split(", ")
-            .map(|part| part.parse().unwrap())
-            .collect();
-        let operation = iter.next().unwrap().into();
-        let test = iter.into();
+        let (items, operation, diviser, on_true, on_false): (String, String, _, _, _) =
+            pattern::parse(
+                "Monkey {_}:
+                   Starting items: {}
+                   Operation: new = {}
+                   Test: divisible by {}
+                     If true: throw to monkey {}
+                     If false: throw to monkey {}",
+                str,
+            )?;
+        let items = items
+            .split(',')
+            .map(|item| {
+                item.trim()
+                    .parse()
+                    .map_err(|error| format!("item {:?}: {}", item, error))
+            })
+            .collect::<Result<_, _>>()?;
+        let operation = operation.parse()?;
+        let test = Test {
+            diviser,
+            on_true,
+            on_false,
+        };
         Ok(Monkey {
             items,
             operation,
@@ -102,42 +118,112 @@ impl Monkey {
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 11\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rounds",
+            part: 1,
+            default: 20,
+            min: 0,
+            max: i64::MAX,
+            description: "Rounds of keep away to play",
+        },
+        Param {
+            name: "rounds",
+            part: 2,
+            default: 10_000,
+            min: 0,
+            max: i64::MAX,
+            description: "Rounds of keep away to play",
+        },
+    ];
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_split_on_empty_line_into_all()?)
+    }
 
-fn input() -> Vec<Monkey> {
-    reader::open("files/day11.txt").split_on_empty_line_into()
+    fn part_one(input: Self::Input) -> Answer {
+        Self::part_one_with(input, &Params::defaults(Self::PARAMS, 1))
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Self::part_two_with(input, &Params::defaults(Self::PARAMS, 2))
+    }
+
+    fn part_one_with(input: Self::Input, params: &Params) -> Answer {
+        part_one(input, params.get("rounds")).into()
+    }
+
+    fn part_two_with(input: Self::Input, params: &Params) -> Answer {
+        part_two(input, params.get("rounds")).into()
+    }
 }
 
-fn part_one(monkeys: Vec<Monkey>) -> usize {
+pub fn part_one(monkeys: Vec<Monkey>, rounds: usize) -> usize {
     let mut monkeys = monkeys;
-    for _ in 0..20 {
+    for _ in 0..rounds {
         play_round(&mut monkeys, |worry| worry / 3);
     }
     monkey_business_level(monkeys)
 }
 
-fn part_two(monkeys: Vec<Monkey>) -> usize {
+/// Items do not affect each other, so every item is followed on its own until
+/// its monkey and worry level repeat at the start of a round, and the
+/// inspections of the remaining rounds are extrapolated from that cycle.
+pub fn part_two(monkeys: Vec<Monkey>, rounds: usize) -> usize {
     let mut monkeys = monkeys;
     let modulus: usize = monkeys.iter().map(|monkey| monkey.diviser()).product();
-    for _ in 0..10_000 {
-        play_round(&mut monkeys, |worry| worry % modulus);
+    let mut counted = vec![0; monkeys.len()];
+    for (holder, monkey) in monkeys.iter().enumerate() {
+        for item in &monkey.items {
+            // Monkeys inspecting the item in every round.
+            let mut inspections = Vec::new();
+            let step = |(holder, worry): &mut (usize, usize)| {
+                let mut round = Vec::new();
+                loop {
+                    round.push(*holder);
+                    *worry = monkeys[*holder].calculate_worry_level(*worry) % modulus;
+                    let next = monkeys[*holder].get_next_monkey(*worry);
+                    // Monkeys that already had their turn get the item back next round.
+                    let next_round = next <= *holder;
+                    *holder = next;
+                    if next_round {
+                        break;
+                    }
+                }
+                inspections.push(round);
+            };
+            let history = cycle::hashed((holder, *item), rounds, step, |state| *state, |_| 0);
+            let (reduced, cycles) = match history.cycle {
+                Some(cycle) => cycle.reduce(rounds),
+                None => (rounds, 0),
+            };
+            for (round, holders) in inspections.iter().enumerate() {
+                let in_cycle = history.cycle.filter(|cycle| round >= cycle.start);
+                let times = usize::from(round < reduced) + in_cycle.map_or(0, |_| cycles);
+                for holder in holders {
+                    counted[*holder] += times;
+                }
+            }
+        }
+    }
+    for (monkey, counted) in monkeys.iter_mut().zip(counted) {
+        monkey.counted = counted;
     }
     monkey_business_level(monkeys)
 }
 
-fn play_round(monkeys: &mut Vec<Monkey>, relax_method: impl Fn(usize) -> usize) {
+fn play_round(monkeys: &mut [Monkey], relax_method: impl Fn(usize) -> usize) {
     for i in 0..monkeys.len() {
-        for item in monkeys[i].items.to_owned() {
+        for item in std::mem::take(&mut monkeys[i].items) {
             monkeys[i].counted += 1;
             let worry_level = relax_method(monkeys[i].calculate_worry_level(item));
             let next_monkey = monkeys[i].get_next_monkey(worry_level);
-            monkeys[i].items.pop();
             monkeys[next_monkey].items.push(worry_level);
         }
     }
@@ -145,7 +231,7 @@ fn play_round(monkeys: &mut Vec<Monkey>, relax_method: impl Fn(usize) -> usize)
 
 fn monkey_business_level(monkeys: Vec<Monkey>) -> usize {
     let mut monkeys = monkeys;
-    monkeys.sort_by(|a, b| b.counted.cmp(&a.counted));
+    monkeys.sort_by_key(|monkey| std::cmp::Reverse(monkey.counted));
     monkeys
         .into_iter()
         .map(|monkey| monkey.counted)
@@ -155,12 +241,12 @@ fn monkey_business_level(monkeys: Vec<Monkey>) -> usize {
 
 #[test]
 fn test_part_one() {
-    assert_eq!(part_one(get_test_input()), 10_605);
+    assert_eq!(part_one(get_test_input(), 20), 10_605);
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 2_713_310_158);
+    assert_eq!(part_two(get_test_input(), 10_000), 2_713_310_158);
 }
 
 #[cfg(test)]
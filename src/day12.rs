@@ -1,132 +1,98 @@
+use crate::error::Error;
+use crate::grid::{Grid, Position};
 use crate::reader;
-use std::collections::{HashSet, VecDeque};
-
-type Point = (usize, usize);
+use crate::search::{self, Path};
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug)]
-struct Heigthmap {
-    start: Point,
-    end: Point,
-    data: Vec<Vec<usize>>,
+pub struct Heigthmap {
+    start: Position,
+    end: Position,
+    data: Grid<usize>,
 }
 
-impl From<String> for Heigthmap {
-    fn from(input: String) -> Self {
+impl TryFrom<reader::Reader> for Heigthmap {
+    type Error = Error;
+
+    fn try_from(input: reader::Reader) -> Result<Self, Self::Error> {
         let mut start = (0, 0);
         let mut end = (0, 0);
-        Self {
-            data: input
-                .lines()
-                .enumerate()
-                .map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .map(|(x, char)| {
-                            1 + match char {
-                                'a'..='z' => (char as usize) - ('a' as usize),
-                                'S' => {
-                                    start = (x, y);
-                                    0
-                                }
-                                'E' => {
-                                    end = (x, y);
-                                    ('z' as usize) - ('a' as usize)
-                                }
-                                _ => panic!("Unexpected charachter '{}'", char),
-                            }
-                        })
-                        .collect()
-                })
-                .collect(),
-            start,
-            end,
-        }
+        let data = input.try_grid(|position, char| {
+            let height = match char {
+                'a'..='z' => (char as usize) - ('a' as usize),
+                'S' => {
+                    start = position;
+                    0
+                }
+                'E' => {
+                    end = position;
+                    ('z' as usize) - ('a' as usize)
+                }
+                _ => return Err(format!("unexpected character {:?}", char)),
+            };
+            Ok(1 + height)
+        })?;
+        Ok(Self { data, start, end })
     }
 }
 
 impl Heigthmap {
-    fn bfs(&self, win_condition: impl Fn(&Self, Point) -> bool) -> usize {
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
-        queue.push_front((self.end, 0));
-        while let Some((point, moves)) = queue.pop_front() {
-            if win_condition(self, point) {
-                return moves;
-            }
-
-            let heigth = self.get_point(point);
-            for next_point in self.get_moves(point) {
-                let next_heigth = self.get_point(next_point);
-                let in_range = heigth - 1 <= next_heigth;
-                if in_range && visited.insert(next_point) {
-                    queue.push_back((next_point, (moves + 1)));
-                }
-            }
-        }
-        panic!("Did not reach last tile");
+    /// Shortest route walking down from the end to the first point matching
+    /// `win_condition`.
+    fn bfs(
+        &self,
+        win_condition: impl Fn(&Self, Position) -> bool,
+    ) -> Option<Path<Position, usize>> {
+        let moves = |point: &Position| {
+            let heigth = self.data[*point];
+            let moves = self.data.neighbours4(*point);
+            moves.filter(move |next| heigth - 1 <= self.data[*next])
+        };
+        search::bfs(self.end, moves, |point| win_condition(self, *point))
     }
+}
 
-    fn get_moves(&self, point: Point) -> Vec<Point> {
-        let mut result = Vec::with_capacity(4);
-        if point.0 > 0 {
-            result.push((point.0 - 1, point.1));
-        }
-        if point.0 + 1 < self.width() {
-            result.push((point.0 + 1, point.1));
-        }
-        if point.1 > 0 {
-            result.push((point.0, point.1 - 1));
-        }
-        if point.1 + 1 < self.heigth() {
-            result.push((point.0, point.1 + 1));
-        }
-        result
-    }
+pub struct Day12;
 
-    fn width(&self) -> usize {
-        self.data[0].len()
-    }
+impl Solution for Day12 {
+    type Input = Heigthmap;
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
 
-    fn heigth(&self) -> usize {
-        self.data.len()
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        input.try_into()
     }
 
-    fn get_point(&self, (x, y): Point) -> usize {
-        self.data[y][x]
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).expect("No route to the best signal").into()
     }
-}
 
-pub fn run() {
-    println!(
-        "Day 12\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
-
-fn input() -> Heigthmap {
-    reader::open("files/day12.txt").text().into()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).expect("No route to the best signal").into()
+    }
 }
 
-fn part_one(heigthmap: Heigthmap) -> usize {
-    heigthmap.bfs(|map, point| point == map.start)
+/// Fewest steps from the start to the end, `None` if the end is out of reach.
+pub fn part_one(heigthmap: Heigthmap) -> Option<usize> {
+    Some(heigthmap.bfs(|map, point| point == map.start)?.cost)
 }
 
-fn part_two(heigthmap: Heigthmap) -> usize {
-    heigthmap.bfs(|map, point| map.get_point(point) == 1)
+/// Fewest steps from any lowest square to the end.
+pub fn part_two(heigthmap: Heigthmap) -> Option<usize> {
+    Some(heigthmap.bfs(|map, point| map.data[point] == 1)?.cost)
 }
 
 #[test]
 fn test_part_one() {
-    assert_eq!(part_one(get_test_input()), 31);
+    assert_eq!(part_one(get_test_input()), Some(31));
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 29);
+    assert_eq!(part_two(get_test_input()), Some(29));
 }
 
 #[cfg(test)]
 fn get_test_input() -> Heigthmap {
-    reader::open("files/day12_test.txt").text().into()
+    reader::open("files/day12_test.txt").try_into().unwrap()
 }
@@ -1,7 +1,8 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::cmp::Ordering;
 use std::str::FromStr;
-use std::string::ParseError;
 
 #[derive(Debug)]
 enum Signal {
@@ -23,35 +24,85 @@ impl Signal {
     }
 }
 
-struct Packet {
+pub struct Packet {
     left: String,
     right: String,
 }
 
 impl FromStr for Packet {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let mut iter = str.lines();
-        Ok(Self {
-            left: iter.next().unwrap().to_string(),
-            right: iter.next().unwrap().to_string(),
-        })
+        match str.lines().collect::<Vec<&str>>()[..] {
+            [left, right] => Ok(Self {
+                left: validate(left)?.to_string(),
+                right: validate(right)?.to_string(),
+            }),
+            _ => Err("expected a pair of packets on two lines".to_string()),
+        }
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 13\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
+/// Check that the packet is a list of numbers and lists, the comparisons
+/// work on the text and expect it to be well formed.
+fn validate(packet: &str) -> Result<&str, String> {
+    match validate_list(packet)? {
+        "" => Ok(packet),
+        rest => Err(format!("unexpected {:?} after the packet", rest)),
+    }
 }
 
-fn input() -> Vec<Packet> {
-    reader::open("files/day13.txt").split_on_empty_line_into()
+/// Check the list at the start of the text, returning the text after it.
+fn validate_list(list: &str) -> Result<&str, String> {
+    let mut rest = list
+        .strip_prefix('[')
+        .ok_or_else(|| format!("expected a list at {:?}", list))?;
+    if let Some(rest) = rest.strip_prefix(']') {
+        return Ok(rest);
+    }
+    loop {
+        rest = match rest.starts_with('[') {
+            true => validate_list(rest)?,
+            false => {
+                let digits =
+                    rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                rest[..digits]
+                    .parse::<usize>()
+                    .map_err(|_| format!("expected a number or a list at {:?}", rest))?;
+                &rest[digits..]
+            }
+        };
+        match rest.strip_prefix(',') {
+            Some(next) => rest = next,
+            None => {
+                return rest
+                    .strip_prefix(']')
+                    .ok_or_else(|| format!("expected ',' or ']' at {:?}", rest))
+            }
+        }
+    }
 }
 
-fn part_one(packets: Vec<Packet>) -> usize {
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Packet>;
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_split_on_empty_line_into_all()?)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
+}
+
+pub fn part_one(packets: Vec<Packet>) -> usize {
     packets
         .into_iter()
         .enumerate()
@@ -60,7 +111,7 @@ fn part_one(packets: Vec<Packet>) -> usize {
         .sum()
 }
 
-fn part_two(packets: Vec<Packet>) -> usize {
+pub fn part_two(packets: Vec<Packet>) -> usize {
     let size = (packets.len() + 1) * 2;
     packets
         .into_iter()
@@ -137,7 +188,7 @@ fn split_value(value: &str) -> (usize, &str) {
 
 fn get_next(input: &str) -> &str {
     let mut open = 0;
-    for (i, char) in input.chars().enumerate() {
+    for (i, char) in input.char_indices() {
         match (char, open) {
             ('[', _) => open += 1,
             (']', 1) => return &input[1..i],
@@ -145,7 +196,7 @@ fn get_next(input: &str) -> &str {
             _ => (),
         }
     }
-    panic!("Unable to get next block {:?}", input);
+    unreachable!("Packets are validated when parsed, got {:?}", input);
 }
 
 #[test]
@@ -162,3 +213,12 @@ fn test_part_two() {
 fn get_test_input() -> Vec<Packet> {
     reader::open("files/day13_test.txt").split_on_empty_line_into()
 }
+
+#[test]
+fn test_validate() {
+    assert!(validate("[1,[2,[]],10]").is_ok());
+    assert!(validate("[1,[2]").is_err());
+    assert!(validate("[1,,2]").is_err());
+    assert!(validate("[1]]").is_err());
+    assert!(validate("[a]").is_err());
+}
@@ -1,10 +1,44 @@
+use crate::error::Error;
+use crate::geometry::Point2;
+use crate::grid::Grid;
 use crate::reader;
-use std::collections::HashSet;
+use crate::solution::{Answer, Solution};
 use std::str::FromStr;
-use std::string::ParseError;
 
-type Point = (isize, isize);
-type Map = HashSet<Point>;
+type Point = Point2<isize>;
+
+/// Blocked tiles of the cave, every tile outside of the grid is open.
+struct Map {
+    blocked: Grid<bool>,
+    left: isize,
+}
+
+impl Map {
+    /// Map wide enough for the sand piling up from `(500, 0)` to a floor at
+    /// `floor`, and for every point of the scans.
+    fn new(scans: &[Scan], floor: isize) -> Self {
+        let xs = scans
+            .iter()
+            .flat_map(|scan| scan.points.iter().map(|p| p.x));
+        let left = xs.clone().min().unwrap_or(500).min(500 - floor) - 1;
+        let right = xs.max().unwrap_or(500).max(500 + floor) + 1;
+        let size = |n: isize| usize::try_from(n).unwrap();
+        Self {
+            blocked: Grid::filled(size(right - left + 1), size(floor + 1), false),
+            left,
+        }
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        let blocked = self.blocked.get_signed((point.x - self.left, point.y));
+        blocked.copied().unwrap_or(false)
+    }
+
+    fn insert(&mut self, point: Point) {
+        let position = (point - Point::new(self.left, 0)).try_cast().unwrap();
+        self.blocked[position.into()] = true;
+    }
+}
 
 enum Bottom {
     Void(isize),
@@ -25,90 +59,98 @@ impl Bottom {
 }
 
 #[derive(Debug)]
-struct Scan {
+pub struct Scan {
     points: Vec<Point>,
 }
 
 impl FromStr for Scan {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let point = |part: &str| {
+            let (x, y) = part.split_once(',').ok_or("expected a point 'x,y'")?;
+            match (x.parse(), y.parse()) {
+                (Ok(x), Ok(y)) => Ok(Point::new(x, y)),
+                _ => Err(format!("invalid point {:?}", part)),
+            }
+        };
         Ok(Self {
-            points: str
-                .split(" -> ")
-                .map(|part| {
-                    let (x, y) = part.split_once(',').unwrap();
-                    (x.parse().unwrap(), y.parse().unwrap())
-                })
-                .collect(),
+            points: str.split(" -> ").map(point).collect::<Result<_, _>>()?,
         })
     }
 }
 
 impl Scan {
-    fn draw(&self, map: &mut HashSet<Point>) -> isize {
-        let mut max_y_value = 0;
+    fn draw(&self, map: &mut Map) {
         for window in self.points.windows(2) {
             let (start, end) = (&window[0], &window[1]);
-            let min_x = std::cmp::min(start.0, end.0);
-            let max_x = std::cmp::max(start.0, end.0);
-            let min_y = std::cmp::min(start.1, end.1);
-            let max_y = std::cmp::max(start.1, end.1);
-            max_y_value = std::cmp::max(max_y_value, max_y);
+            let min_x = std::cmp::min(start.x, end.x);
+            let max_x = std::cmp::max(start.x, end.x);
+            let min_y = std::cmp::min(start.y, end.y);
+            let max_y = std::cmp::max(start.y, end.y);
             for y in min_y..=max_y {
                 for x in min_x..=max_x {
-                    map.insert((x, y));
+                    map.insert(Point::new(x, y));
                 }
             }
         }
-        max_y_value
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 14\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Scan>;
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
 
-fn input() -> Vec<Scan> {
-    reader::open("files/day14.txt").lines_as()
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(scans: Vec<Scan>) -> usize {
+pub fn part_one(scans: Vec<Scan>) -> usize {
     let (y, mut map) = scans_to_map(scans);
     let bottom = Bottom::Void(y + 1);
     (0..).find(|_| !drop(&bottom, &mut map)).unwrap()
 }
 
-fn part_two(scans: Vec<Scan>) -> usize {
+pub fn part_two(scans: Vec<Scan>) -> usize {
     let (y, mut map) = scans_to_map(scans);
     let bottom = Bottom::Floor(y + 2);
     (0..).find(|_| !drop(&bottom, &mut map)).unwrap()
 }
 
 fn scans_to_map(scans: Vec<Scan>) -> (isize, Map) {
-    let mut map = HashSet::new();
-    let mut max_y = 0;
+    let ys = scans
+        .iter()
+        .flat_map(|scan| scan.points.iter().map(|p| p.y));
+    let max_y = ys.max().unwrap_or(0);
+    let mut map = Map::new(&scans, max_y + 2);
     for scan in scans {
-        let cur_y = scan.draw(&mut map);
-        max_y = std::cmp::max(max_y, cur_y);
+        scan.draw(&mut map);
     }
     (max_y, map)
 }
 
 fn drop(bottom: &Bottom, map: &mut Map) -> bool {
-    let mut point = (500, 0);
+    let mut point = Point::new(500, 0);
     if map.contains(&point) {
         return false;
     }
     while let Some(next) = try_get_next_point(&point, map) {
-        if next.1 >= bottom.value() {
+        if next.y >= bottom.value() {
             if bottom.is_floor() {
                 break;
             } else {
-                return false
+                return false;
             }
         }
         point = next;
@@ -117,13 +159,11 @@ fn drop(bottom: &Bottom, map: &mut Map) -> bool {
     true
 }
 
-fn try_get_next_point((x, y): &Point, map: &Map) -> Option<Point> {
-    for (n, m) in [(0, 1), (-1, 1), (1, 1)] {
-        if !map.contains(&(x + n, y + m)) {
-            return Some((x + n, y + m))
-        }
-    }
-    None
+fn try_get_next_point(point: &Point, map: &Map) -> Option<Point> {
+    [(0, 1), (-1, 1), (1, 1)]
+        .into_iter()
+        .map(|offset| *point + Point::from(offset))
+        .find(|next| !map.contains(next))
 }
 
 #[test]
@@ -1,86 +1,118 @@
+use crate::cancel;
+use crate::error::Error;
+use crate::geometry::Point2;
+use crate::interval::IntervalSet;
+use crate::pattern;
 use crate::reader;
+use crate::solution::{Answer, Param, Params, Solution};
 use std::str::FromStr;
-use std::string::ParseError;
 
-type Point = (isize, isize);
+type Point = Point2<isize>;
 
 #[derive(Debug)]
-struct Sensor {
+pub struct Sensor {
     position: Point,
+    beacon: Point,
     distance: isize,
 }
 
 impl FromStr for Sensor {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (sensor_str, beacon_str) = str.split_once(':').unwrap();
-        let (sx, sy) = sensor_str[10..].split_once(", ").unwrap();
-        let (bx, by) = beacon_str[22..].split_once(", ").unwrap();
-        let (sx, sy): Point = (sx[2..].parse().unwrap(), sy[2..].parse().unwrap());
-        let (bx, by): Point = (bx[2..].parse().unwrap(), by[2..].parse().unwrap());
+        let (sx, sy, bx, by) =
+            pattern::parse("Sensor at x={}, y={}: closest beacon is at x={}, y={}", str)?;
+        let sensor = Point::new(sx, sy);
+        let beacon = Point::new(bx, by);
         Ok(Self {
-            position: (sx, sy),
-            distance: (sx - bx).abs() + (sy - by).abs(),
+            position: sensor,
+            beacon,
+            distance: sensor.manhattan(beacon),
         })
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 15\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input(), 2_000_000),
-        part_two(input(), 4_000_000)
-    );
-}
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "row",
+            part: 1,
+            default: 2_000_000,
+            min: i64::MIN,
+            max: i64::MAX,
+            description: "Row in which to count the positions without a beacon",
+        },
+        Param {
+            name: "bound",
+            part: 2,
+            default: 4_000_000,
+            min: 0,
+            max: i64::MAX,
+            description: "Largest coordinate of the distress beacon",
+        },
+    ];
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        Self::part_one_with(input, &Params::defaults(Self::PARAMS, 1))
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Self::part_two_with(input, &Params::defaults(Self::PARAMS, 2))
+    }
 
-fn input() -> Vec<Sensor> {
-    reader::open("files/day15.txt").lines_as()
+    fn part_one_with(input: Self::Input, params: &Params) -> Answer {
+        part_one(input, params.get("row")).into()
+    }
+
+    fn part_two_with(input: Self::Input, params: &Params) -> Answer {
+        part_two(input, params.get("bound")).into()
+    }
 }
 
-fn part_one(pairs: Vec<Sensor>, y: isize) -> isize {
-    let mut ranges = Vec::with_capacity(pairs.len());
-    fill_ranges(&mut ranges, &pairs, y, isize::MIN, isize::MAX);
-    let result = eval_range(&mut ranges);
-    result.1 - result.0
+pub fn part_one(pairs: Vec<Sensor>, y: isize) -> isize {
+    let mut covered = IntervalSet::new();
+    fill_ranges(&mut covered, &pairs, y);
+    let mut beacons = pairs
+        .iter()
+        .map(|sensor| sensor.beacon)
+        .filter(|beacon| beacon.y == y)
+        .collect::<Vec<Point>>();
+    beacons.sort();
+    beacons.dedup();
+    (covered.len() - beacons.len() as i64) as isize
 }
 
-fn part_two(pairs: Vec<Sensor>, max: isize) -> isize {
-    let mut ranges = Vec::with_capacity(pairs.len());
+pub fn part_two(pairs: Vec<Sensor>, max: isize) -> isize {
+    let mut covered = IntervalSet::new();
     for y in 0..=max {
-        fill_ranges(&mut ranges, &pairs, y, 0, max);
-        let result = eval_range(&mut ranges);
-        if result.1 != max {
-            return (result.1 + 1) * 4000000 + y;
+        cancel::check();
+        fill_ranges(&mut covered, &pairs, y);
+        if let Some(gap) = covered.gaps(0..max as i64 + 1).next() {
+            return gap.start as isize * 4000000 + y;
         }
     }
     0
 }
 
-fn fill_ranges(ranges: &mut Vec<Point>, sensors: &Vec<Sensor>, y: isize, min: isize, max: isize) {
-    ranges.clear();
+/// Positions of the row within reach of any of the sensors.
+fn fill_ranges(covered: &mut IntervalSet, sensors: &Vec<Sensor>, y: isize) {
+    covered.clear();
     for sensor in sensors {
-        let y_offset = (y - sensor.position.1).abs();
+        let y_offset = (y - sensor.position.y).abs();
         if y_offset <= sensor.distance {
-            let left = std::cmp::max(min, (sensor.position.0 - sensor.distance) + y_offset);
-            let right = std::cmp::min(max, (sensor.position.0 + sensor.distance) - y_offset);
-            if left <= right {
-                ranges.push((left, right));
-            }
-        }
-    }
-}
-
-fn eval_range(ranges: &mut [Point]) -> Point {
-    ranges.sort();
-    let (left, mut right) = (ranges[0].0, ranges[0].1);
-    for range in ranges.iter().skip(1) {
-        if range.0 <= right + 1 {
-            right = std::cmp::max(right, range.1);
-        } else {
-            return (left, right);
+            let left = (sensor.position.x - sensor.distance) + y_offset;
+            let right = (sensor.position.x + sensor.distance) - y_offset;
+            covered.insert(left as i64..right as i64 + 1);
         }
     }
-    (left, right)
 }
 
 #[test]
@@ -1,10 +1,14 @@
+use crate::cancel;
+use crate::error::Error;
+use crate::pattern;
 use crate::reader;
-use std::collections::{HashMap, HashSet, LinkedList};
+use crate::search;
+use crate::solution::{Answer, Param, Params, Solution};
+use std::collections::HashMap;
 use std::str::FromStr;
-use std::string::ParseError;
 
 #[derive(Debug)]
-struct Valve {
+pub struct Valve {
     name: String,
     output: usize,
     named_outputs: Vec<String>,
@@ -13,13 +17,15 @@ struct Valve {
 }
 
 impl FromStr for Valve {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (left, right) = str.split_once(';').unwrap();
+        // Both "tunnels lead to valves AA, BB" and "tunnel leads to valve AA".
+        let (name, output, valves): (String, usize, String) =
+            pattern::parse("Valve {} has flow rate={}; {_} {_} to {_} {}", str)?;
         Ok(Self {
-            name: left[6..8].to_string(),
-            output: left[23..].parse().unwrap(),
-            named_outputs: right[23..]
+            name,
+            output,
+            named_outputs: valves
                 .split(',')
                 .map(|part| part.trim().to_string())
                 .collect(),
@@ -30,13 +36,13 @@ impl FromStr for Valve {
 }
 
 trait ValveUtils {
-    fn index_of(&self, name: &String) -> usize;
+    fn index_of(&self, name: &str) -> usize;
 }
 
 impl ValveUtils for Vec<Valve> {
-    fn index_of(&self, name: &String) -> usize {
+    fn index_of(&self, name: &str) -> usize {
         for (i, valve) in self.iter().enumerate() {
-            if &valve.name == name {
+            if valve.name == name {
                 return i;
             }
         }
@@ -44,31 +50,66 @@ impl ValveUtils for Vec<Valve> {
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 16\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<Valve>;
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "minutes",
+            part: 1,
+            default: 30,
+            min: 0,
+            max: i64::MAX,
+            description: "Minutes until the volcano erupts",
+        },
+        Param {
+            name: "minutes",
+            part: 2,
+            default: 26,
+            min: 0,
+            max: i64::MAX,
+            description: "Minutes left after teaching the elephant",
+        },
+    ];
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        Self::part_one_with(input, &Params::defaults(Self::PARAMS, 1))
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Self::part_two_with(input, &Params::defaults(Self::PARAMS, 2))
+    }
+
+    fn part_one_with(input: Self::Input, params: &Params) -> Answer {
+        part_one(input, params.get("minutes")).into()
+    }
 
-fn input() -> Vec<Valve> {
-    reader::open("files/day16.txt").lines_as()
+    fn part_two_with(input: Self::Input, params: &Params) -> Answer {
+        part_two(input, params.get("minutes")).into()
+    }
 }
 
-fn part_one(valves: Vec<Valve>) -> usize {
+pub fn part_one(valves: Vec<Valve>, minutes: isize) -> usize {
     let valves = process(valves);
-    dfs(30, valves.len() - 1, &0, &valves, &mut HashMap::new())
+    dfs(minutes, valves.len() - 1, &0, &valves, &mut HashMap::new())
 }
 
-fn part_two(valves: Vec<Valve>) -> usize {
+pub fn part_two(valves: Vec<Valve>, minutes: isize) -> usize {
     let valves = process(valves);
     let mut cache = HashMap::new();
     let mut max = 0;
     let end = (1 << (valves.len() - 1)) - 1;
     for i in 0..(end + 1) / 2 {
-        let x = dfs(26, valves.len() - 1, &i, &valves, &mut cache);
-        let y = dfs(26, valves.len() - 1, &(end ^ i), &valves, &mut cache);
+        cancel::check();
+        let x = dfs(minutes, valves.len() - 1, &i, &valves, &mut cache);
+        let y = dfs(minutes, valves.len() - 1, &(end ^ i), &valves, &mut cache);
         max = std::cmp::max(max, x + y);
     }
     max
@@ -83,7 +124,7 @@ fn new_mask(position: usize, open_valves: usize) -> usize {
 }
 
 fn process(mut valves: Vec<Valve>) -> Vec<Valve> {
-    let first = valves.remove(valves.index_of(&"AA".to_string()));
+    let first = valves.remove(valves.index_of("AA"));
     valves.push(first);
     for index in 0..valves.len() {
         valves[index].n_outputs = reduce_edges(valves[index].name.to_string(), &valves);
@@ -105,27 +146,14 @@ fn process(mut valves: Vec<Valve>) -> Vec<Valve> {
     output
 }
 
+/// Distances to every other valve with a positive flow rate.
 fn reduce_edges(initial: String, lookup: &Vec<Valve>) -> Vec<(String, isize)> {
-    let mut result = Vec::new();
-    let mut visited = HashSet::new();
-    let mut queue = LinkedList::new();
-    queue.push_back((initial, 1));
-
-    while let Some((current, distance)) = queue.pop_front() {
-        for edge in lookup[lookup.index_of(&current)].named_outputs.to_vec() {
-            if visited.contains(&edge) {
-                continue;
-            }
-            visited.insert(edge.to_string());
-
-            if lookup[lookup.index_of(&edge)].output > 0 {
-                result.push((edge.to_string(), distance));
-            }
-            queue.push_back((edge.to_string(), distance + 1));
-        }
-    }
-
-    result
+    let tunnels = |name: &String| lookup[lookup.index_of(name)].named_outputs.clone();
+    search::explore(initial, tunnels)
+        .iter()
+        .filter(|(name, distance)| *distance > 0 && lookup[lookup.index_of(name)].output > 0)
+        .map(|(name, distance)| (name.to_string(), distance as isize))
+        .collect()
 }
 
 fn dfs(
@@ -140,6 +168,7 @@ fn dfs(
         return *known_result;
     }
 
+    cancel::check();
     let mut result = 0;
     for (next, w) in &valve_lookup[index].i_outputs {
         if !can_open(*next, *open_valves) {
@@ -163,12 +192,12 @@ fn dfs(
 
 #[test]
 fn test_part_one() {
-    assert_eq!(part_one(get_test_input()), 1651);
+    assert_eq!(part_one(get_test_input(), 30), 1651);
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 1707);
+    assert_eq!(part_two(get_test_input(), 26), 1707);
 }
 
 #[cfg(test)]
@@ -1,45 +1,113 @@
+use crate::cycle;
+use crate::error::Error;
 use crate::reader;
-use std::collections::HashMap;
+use crate::solution::{Answer, Param, Params, Solution};
 
 type Shape = Vec<usize>;
 
-pub fn run() {
-    println!(
-        "Day 17\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = String;
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Pyroclastic Flow";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rocks",
+            part: 1,
+            default: 2022,
+            min: 0,
+            max: i64::MAX,
+            description: "Number of rocks to drop",
+        },
+        Param {
+            name: "rocks",
+            part: 2,
+            default: 1_000_000_000_000,
+            min: 0,
+            max: i64::MAX,
+            description: "Number of rocks to drop",
+        },
+    ];
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.text())
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        Self::part_one_with(input, &Params::defaults(Self::PARAMS, 1))
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Self::part_two_with(input, &Params::defaults(Self::PARAMS, 2))
+    }
 
-fn input() -> String {
-    reader::open("files/day17.txt").text()
+    fn part_one_with(input: Self::Input, params: &Params) -> Answer {
+        part_one(input, params.get("rocks")).into()
+    }
+
+    fn part_two_with(input: Self::Input, params: &Params) -> Answer {
+        part_two(input, params.get("rocks")).into()
+    }
 }
 
-fn part_one(values: String) -> usize {
-    play(values, 2022)
+pub fn part_one(values: String, rocks: usize) -> usize {
+    play(values, rocks)
 }
 
-fn part_two(values: String) -> usize {
-    play(values, 1_000_000_000_000)
+pub fn part_two(values: String, rocks: usize) -> usize {
+    play(values, rocks)
 }
 
 fn play(rules: String, goal_rock_count: usize) -> usize {
-    let shapes = get_shapes();
-    let mut rule_iter = rules.chars().into_iter().cycle().peekable();
-    let mut shape_iter = shapes.iter().enumerate().cycle();
-    let mut stack = Vec::with_capacity(4000);
-    let mut mem = HashMap::new();
-    let mut dropped_rocks = 0;
-    let mut skipped = 0;
-
-    while dropped_rocks < goal_rock_count {
-        let (id, original_shape) = shape_iter.next().unwrap();
-        let initial_move = *rule_iter.peek().unwrap();
+    let history = cycle::hashed(
+        Chamber::new(&rules),
+        goal_rock_count,
+        Chamber::drop_rock,
+        Chamber::fingerprint,
+        |chamber| chamber.stack.len() as i64,
+    );
+    history.at(goal_rock_count).unwrap() as usize
+}
+
+/// Rows of the rocks that came to rest, one bit per column, and the next
+/// shape and jet.
+struct Chamber {
+    jets: Vec<char>,
+    jet: usize,
+    shapes: Vec<Shape>,
+    shape: usize,
+    stack: Vec<usize>,
+}
+
+impl Chamber {
+    fn new(rules: &str) -> Self {
+        Self {
+            jets: rules.trim().chars().collect(),
+            jet: 0,
+            shapes: get_shapes(),
+            shape: 0,
+            stack: Vec::with_capacity(4000),
+        }
+    }
+
+    /// The next shape and jet with the top rows of the stack, which in
+    /// practice decide how the next rocks fall.
+    fn fingerprint(&self) -> (usize, usize, Vec<usize>) {
+        let start = self.stack.len().saturating_sub(30);
+        (self.shape, self.jet, self.stack[start..].to_owned())
+    }
+
+    fn drop_rock(&mut self) {
+        let original_shape = &self.shapes[self.shape];
+        self.shape = (self.shape + 1) % self.shapes.len();
+        let stack = &mut self.stack;
         let shape_height = original_shape.len();
         stack.extend((0..3 + shape_height).map(|_| 0));
         let mut current_shape = original_shape.to_vec();
         'inifine: for y in 0.. {
-            let rule = rule_iter.next().unwrap();
+            let rule = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
             let end = stack.len() - y;
             let start = end - shape_height;
 
@@ -80,25 +148,7 @@ fn play(rules: String, goal_rock_count: usize) -> usize {
         while !stack.is_empty() && stack[stack.len() - 1] == 0 {
             stack.pop();
         }
-
-        let start = std::cmp::max(0, stack.len() as isize - 30) as usize;
-        let sample = stack[start..stack.len()].to_owned();
-        let key = (id, initial_move, sample);
-
-        if let Some((previous_count, previous_height)) = mem.get(&key) {
-            let heigth_offset = stack.len() - previous_height;
-            let rock_count_offset = dropped_rocks - previous_count;
-            let n = (goal_rock_count - dropped_rocks) / rock_count_offset;
-            skipped += heigth_offset * n;
-            dropped_rocks += rock_count_offset * n;
-        }
-
-        let value = (dropped_rocks, stack.len());
-        mem.insert(key, value);
-
-        dropped_rocks += 1;
     }
-    skipped + stack.len()
 }
 
 fn get_shapes() -> Vec<Shape> {
@@ -111,17 +161,6 @@ fn get_shapes() -> Vec<Shape> {
     ]
 }
 
-#[allow(dead_code)]
-fn draw(stack: &[usize]) {
-    for i in stack.iter().rev() {
-        let x = format!("{:#09b}", i)[2..]
-            .replace('0', ".")
-            .replace('1', "#");
-        println!("|{}|", x);
-    }
-    println!("+-------+");
-}
-
 fn can_shift_in_room(a: &usize, rule: &char) -> bool {
     match rule {
         '<' => a & (1 << 6) == 0,
@@ -144,12 +183,15 @@ fn can_move(shape: &usize, board: &usize, rule: &char) -> bool {
 
 #[test]
 fn test_part_one() {
-    assert_eq!(part_one(get_test_input()), 3068);
+    assert_eq!(part_one(get_test_input(), 2022), 3068);
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 1_514_285_714_288);
+    assert_eq!(
+        part_two(get_test_input(), 1_000_000_000_000),
+        1_514_285_714_288
+    );
 }
 
 #[cfg(test)]
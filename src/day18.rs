@@ -1,50 +1,36 @@
+use crate::error::Error;
+use crate::geometry::Point3;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::collections::{HashMap, HashSet};
 
-type Point = (isize, isize, isize);
+type Point = Point3<isize>;
 
-const DIRECTIONS: [Point; 6] = [
-    (1, 0, 0),
-    (0, 1, 0),
-    (0, 0, 1),
-    (-1, 0, 0),
-    (0, -1, 0),
-    (0, 0, -1),
-];
+pub struct Day18;
 
-trait PointUtil {
-    fn add(&self, other: &Point) -> Point;
-}
+impl Solution for Day18 {
+    type Input = Vec<Point>;
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Boiling Boulders";
 
-impl PointUtil for Point {
-    fn add(&self, other: &Point) -> Point {
-        (self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
     }
-}
-
-pub fn run() {
-    println!(
-        "Day 18\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
 
-fn input() -> Vec<(isize, isize, isize)> {
-    reader::open("files/day18.txt").parse_lines(parse_line)
-}
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
 
-fn parse_line(line: &str) -> Point {
-    let mut iter = line.split(",");
-    let mut next = || iter.next().unwrap().parse().unwrap();
-    (next(), next(), next())
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(points: Vec<Point>) -> usize {
+pub fn part_one(points: Vec<Point>) -> usize {
     count_exposed(&points.into_iter().collect())
 }
 
-fn part_two(points: Vec<Point>) -> usize {
+pub fn part_two(points: Vec<Point>) -> usize {
     let mut points = points.into_iter().collect::<HashSet<Point>>();
     let surrounding_air_blocks = get_surrounding_air_blocks(&points);
     add_internal_air_blocks(&mut points, &surrounding_air_blocks);
@@ -55,9 +41,10 @@ fn count_exposed(points: &HashSet<Point>) -> usize {
     points
         .iter()
         .map(|point| {
-            DIRECTIONS
+            point
+                .neighbours6()
                 .iter()
-                .filter(|dir| !points.contains(&point.add(dir)))
+                .filter(|neighbour| !points.contains(neighbour))
                 .count()
         })
         .sum()
@@ -66,8 +53,7 @@ fn count_exposed(points: &HashSet<Point>) -> usize {
 fn get_surrounding_air_blocks(points: &HashSet<Point>) -> HashMap<Point, usize> {
     let mut result = HashMap::new();
     for point in points {
-        for offset in &DIRECTIONS {
-            let point = point.add(offset);
+        for point in point.neighbours6() {
             if !points.contains(&point) {
                 *result.entry(point).or_insert(0) += 1;
             }
@@ -90,7 +76,7 @@ fn add_internal_air_blocks(
             continue;
         }
 
-        if let Some(air) = dfs(*key, &mut HashSet::new(), &points, &bounds) {
+        if let Some(air) = dfs(*key, &mut HashSet::new(), points, &bounds) {
             internal_air.extend(air)
         }
     }
@@ -101,20 +87,20 @@ fn add_internal_air_blocks(
 }
 
 fn in_bounds(point: &Point, bounds: &Point) -> bool {
-    return point.0 > 0
-        && point.0 <= bounds.0
-        && point.1 > 0
-        && point.1 <= bounds.1
-        && point.2 > 0
-        && point.2 <= bounds.2;
+    point.x > 0
+        && point.x <= bounds.x
+        && point.y > 0
+        && point.y <= bounds.y
+        && point.z > 0
+        && point.z <= bounds.z
 }
 
 fn calculate_bounds(points: &HashSet<Point>) -> Point {
-    let mut bounds = (0, 0, 0);
+    let mut bounds = Point::default();
     for point in points {
-        bounds.0 = std::cmp::max(bounds.0, point.0);
-        bounds.1 = std::cmp::max(bounds.1, point.1);
-        bounds.2 = std::cmp::max(bounds.2, point.2);
+        bounds.x = std::cmp::max(bounds.x, point.x);
+        bounds.y = std::cmp::max(bounds.y, point.y);
+        bounds.z = std::cmp::max(bounds.z, point.z);
     }
     bounds
 }
@@ -133,8 +119,7 @@ fn dfs(
     result.push(current);
     seen.insert(current);
 
-    for offset in &DIRECTIONS {
-        let point = current.add(offset);
+    for point in current.neighbours6() {
         if !in_bounds(&point, bounds) {
             return None;
         }
@@ -163,5 +148,5 @@ fn test_part_two() {
 
 #[cfg(test)]
 fn get_test_input() -> Vec<Point> {
-    reader::open("files/day18_test.txt").parse_lines(parse_line)
+    reader::open("files/day18_test.txt").lines_as()
 }
@@ -1,7 +1,9 @@
+use crate::error::Error;
+use crate::pattern;
 use crate::reader;
-use std::collections::{HashSet, LinkedList};
+use crate::search::{self, Path};
+use crate::solution::{Answer, Param, Params, Solution};
 use std::str::FromStr;
-use std::string::ParseError;
 
 type Ores = Values;
 type Robots = Values;
@@ -20,7 +22,7 @@ fn can_sub(a: Values, b: Values) -> bool {
 }
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
     id: usize,
     ore_robot: Values,
     clay_robot: Values,
@@ -29,16 +31,22 @@ struct Blueprint {
 }
 
 impl FromStr for Blueprint {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (left, right) = str.split_once(':').unwrap();
-        let mut robot_iter = right.split('.');
+        let (id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian) =
+            pattern::parse(
+                "Blueprint {}: Each ore robot costs {} ore. \
+                 Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.",
+                str,
+            )?;
         Ok(Self {
-            id: left[10..].parse().unwrap(),
-            ore_robot: parse_material(robot_iter.next().unwrap()),
-            clay_robot: parse_material(robot_iter.next().unwrap()),
-            obsidian_robot: parse_material(robot_iter.next().unwrap()),
-            geode_robot: parse_material(robot_iter.next().unwrap()),
+            id,
+            ore_robot: (ore, 0, 0, 0),
+            clay_robot: (clay, 0, 0, 0),
+            obsidian_robot: (obsidian_ore, obsidian_clay, 0, 0),
+            geode_robot: (geode_ore, 0, geode_obsidian, 0),
         })
     }
 }
@@ -77,64 +85,74 @@ impl Blueprint {
     }
 }
 
-fn parse_material(input: &str) -> Values {
-    let mut input_iter = input.trim().split(' ').skip(4);
-    let mut materials = (0, 0, 0, 0);
-    loop {
-        let cost = input_iter.next().unwrap().parse().unwrap();
-        let cost_type = input_iter.next().unwrap();
-        let result = match cost_type {
-            "ore" => (cost, 0, 0, 0),
-            "clay" => (0, cost, 0, 0),
-            "obsidian" => (0, 0, cost, 0),
-            _ => panic!("Unkown cost type: '{:?}'", cost_type),
-        };
-        materials = add(materials, result);
-        if input_iter.next().is_none() {
-            break;
-        }
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Not Enough Minerals";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "minutes",
+            part: 1,
+            default: 24,
+            min: 0,
+            max: i64::MAX,
+            description: "Minutes to collect geodes",
+        },
+        Param {
+            name: "minutes",
+            part: 2,
+            default: 32,
+            min: 0,
+            max: i64::MAX,
+            description: "Minutes to collect geodes",
+        },
+    ];
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
     }
-    materials
-}
 
-pub fn run() {
-    println!(
-        "Day 19\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+    fn part_one(input: Self::Input) -> Answer {
+        Self::part_one_with(input, &Params::defaults(Self::PARAMS, 1))
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Self::part_two_with(input, &Params::defaults(Self::PARAMS, 2))
+    }
+
+    fn part_one_with(input: Self::Input, params: &Params) -> Answer {
+        part_one(input, params.get("minutes")).into()
+    }
 
-fn input() -> Vec<Blueprint> {
-    reader::open("files/day19.txt").lines_as()
+    fn part_two_with(input: Self::Input, params: &Params) -> Answer {
+        part_two(input, params.get("minutes")).into()
+    }
 }
 
-fn part_one(blueprints: Vec<Blueprint>) -> usize {
+pub fn part_one(blueprints: Vec<Blueprint>, minutes: usize) -> usize {
     blueprints
         .into_iter()
         .map(|blueprint| {
-            resolve(
-                State::from((0, 0, 0, 0), (1, 0, 0, 0), 24),
-                Refs::from(&blueprint, &mut HashSet::new()),
-            ) * blueprint.id
+            let initial = State::from((0, 0, 0, 0), (1, 0, 0, 0), minutes);
+            geodes(&resolve(initial, Refs::from(&blueprint))) * blueprint.id
         })
         .sum()
 }
 
-fn part_two(values: Vec<Blueprint>) -> usize {
+pub fn part_two(values: Vec<Blueprint>, minutes: usize) -> usize {
     values
         .into_iter()
         .take(3)
         .map(|blueprint| {
-            resolve(
-                State::from((0, 0, 0, 0), (1, 0, 0, 0), 32),
-                Refs::from(&blueprint, &mut HashSet::new()),
-            )
+            let initial = State::from((0, 0, 0, 0), (1, 0, 0, 0), minutes);
+            geodes(&resolve(initial, Refs::from(&blueprint)))
         })
         .product()
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 struct State {
     ores: Ores,
     robots: Robots,
@@ -158,15 +176,13 @@ impl State {
 #[derive(Debug)]
 struct Refs<'a> {
     blueprint: &'a Blueprint,
-    mem: &'a mut HashSet<State>,
     max_consume: Values,
 }
 
 impl<'a> Refs<'a> {
-    fn from(blueprint: &'a Blueprint, mem: &'a mut HashSet<State>) -> Self {
+    fn from(blueprint: &'a Blueprint) -> Self {
         Self {
             blueprint,
-            mem,
             max_consume: (
                 blueprint.max_ore(),
                 blueprint.max_clay(),
@@ -181,48 +197,40 @@ impl<'a> Refs<'a> {
         let (a, b, c, _) = state.robots;
         x < a || y < b || z < c
     }
-
-    fn has_seen_state(&self, state: &State) -> bool {
-        self.mem.get(state).is_some()
-    }
 }
 
-fn resolve(initial: State, refs: Refs) -> usize {
+/// Route through the states to the most geodes that can be opened in time.
+fn resolve(initial: State, refs: Refs) -> Path<State, usize> {
     let (mut max, mut max_time) = (0, initial.time);
-    let mut queue = LinkedList::new();
-    queue.push_back(initial);
-
-    while let Some(state) = queue.pop_front() {
+    let successors = |state: &State| {
         let new_max_value = max < state.ores.3;
         if new_max_value {
             max = std::cmp::max(max, state.ores.3);
             max_time = state.time;
         }
 
-        if refs.has_over_production(&state) {
-            continue;
-        }
-
         let cannot_surpas_max_value = state.ores.3 + 2 < max && state.time <= max_time;
-        if cannot_surpas_max_value {
-            continue;
-        }
-
         let ran_out_of_time = state.time == 0;
-        if ran_out_of_time {
-            continue;
+        if refs.has_over_production(state) || cannot_surpas_max_value || ran_out_of_time {
+            return Vec::new();
         }
 
-        if refs.has_seen_state(&state) {
-            continue;
-        }
+        permute(state.ores, refs.blueprint)
+            .into_iter()
+            .map(|(new_ores, new_robots)| state.next(new_ores, new_robots))
+            .collect()
+    };
+
+    let explored = search::explore(initial, successors);
+    let (best, _) = explored
+        .iter()
+        .max_by_key(|(state, _)| state.ores.3)
+        .expect("the initial state is always explored");
+    explored.path(&best.clone()).unwrap()
+}
 
-        for (new_ores, new_robots) in permute(state.ores, refs.blueprint) {
-            queue.push_back(state.next(new_ores, new_robots));
-        }
-        refs.mem.insert(state);
-    }
-    max
+fn geodes(path: &Path<State, usize>) -> usize {
+    path.nodes.last().map_or(0, |state| state.ores.3)
 }
 
 fn permute(ores: Ores, blueprint: &Blueprint) -> Vec<(Ores, Robots)> {
@@ -254,12 +262,12 @@ fn permute(ores: Ores, blueprint: &Blueprint) -> Vec<(Ores, Robots)> {
 
 #[test]
 fn test_part_one() {
-    assert_eq!(part_one(get_test_input()), 33);
+    assert_eq!(part_one(get_test_input(), 24), 33);
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 56 * 62);
+    assert_eq!(part_two(get_test_input(), 32), 56 * 62);
 }
 
 #[cfg(test)]
@@ -1,20 +1,23 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::str::FromStr;
-use std::string::ParseError;
 
 #[derive(Debug)]
-struct Round {
+pub struct Round {
     elf: Shape,
     player: Shape,
 }
 
 impl FromStr for Round {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (elf, player) = str.split_once(' ').unwrap();
+        let (elf, player) = str
+            .split_once(' ')
+            .ok_or("expected two shapes separated by a space")?;
         Ok(Self {
-            elf: elf.parse().unwrap(),
-            player: player.parse().unwrap(),
+            elf: elf.parse()?,
+            player: player.parse()?,
         })
     }
 }
@@ -62,13 +65,13 @@ enum Shape {
 }
 
 impl FromStr for Shape {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         match str {
             "A" | "X" => Ok(Shape::Rock),
             "B" | "Y" => Ok(Shape::Paper),
             "C" | "Z" => Ok(Shape::Scissors),
-            _ => panic!("Unable create shape from: '{:?}'.", str),
+            _ => Err(format!("unknown shape {:?}", str)),
         }
     }
 }
@@ -79,23 +82,31 @@ impl Shape {
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 2\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Round>;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
 
-fn input() -> Vec<Round> {
-    reader::open("files/day2.txt").lines_as()
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(rounds: Vec<Round>) -> usize {
+pub fn part_one(rounds: Vec<Round>) -> usize {
     rounds.iter().map(|round| round.play_round()).sum()
 }
 
-fn part_two(rounds: Vec<Round>) -> usize {
+pub fn part_two(rounds: Vec<Round>) -> usize {
     rounds
         .iter()
         .map(|round| round.expected_outcome_to_actual().play_round())
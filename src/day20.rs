@@ -1,30 +1,52 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Param, Params, Solution};
 use std::collections::VecDeque;
 
 type Pair = (usize, isize);
 
-pub fn run() {
-    println!(
-        "Day 20\n\tPart 1: {}\n\tPart 2: {}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<Pair>;
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Grove Positioning System";
+    const PARAMS: &'static [Param] = &[Param {
+        name: "key",
+        part: 2,
+        default: 811_589_153,
+        min: -1_000_000_000_000,
+        max: 1_000_000_000_000,
+        description: "Decryption key the numbers are multiplied with",
+    }];
 
-fn input() -> Vec<Pair> {
-    group_input(reader::open("files/day20.txt").lines_as())
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(group_input(input.try_lines_as_all()?))
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        Self::part_two_with(input, &Params::defaults(Self::PARAMS, 2))
+    }
+
+    fn part_two_with(input: Self::Input, params: &Params) -> Answer {
+        part_two(input, params.get("key")).into()
+    }
 }
 
 fn group_input(input: Vec<isize>) -> Vec<Pair> {
     input.into_iter().enumerate().collect()
 }
 
-fn part_one(values: Vec<Pair>) -> isize {
+pub fn part_one(values: Vec<Pair>) -> isize {
     count_result(mix(values, 1, 1))
 }
 
-fn part_two(values: Vec<Pair>) -> isize {
-    count_result(mix(values, 811_589_153, 10))
+pub fn part_two(values: Vec<Pair>, key: isize) -> isize {
+    count_result(mix(values, key, 10))
 }
 
 fn mix(original: Vec<Pair>, decryption_key: isize, mix_times: usize) -> VecDeque<Pair> {
@@ -76,7 +98,7 @@ fn test_part_one() {
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 1_623_178_306);
+    assert_eq!(part_two(get_test_input(), 811_589_153), 1_623_178_306);
 }
 
 #[cfg(test)]
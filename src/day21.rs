@@ -1,7 +1,8 @@
+use crate::error::Error;
 use crate::reader;
-use std::collections::HashMap;
+use crate::solution::{Answer, Solution};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::string::ParseError;
 
 #[derive(Debug)]
 enum Job {
@@ -9,17 +10,19 @@ enum Job {
     Operator(String, String, String),
 }
 
-impl From<&str> for Job {
-    fn from(value: &str) -> Self {
-        if let Ok(value) = value.parse() {
-            Job::Number(value)
-        } else {
-            let mut iter = value.split(' ');
-            Job::Operator(
-                iter.next().unwrap().to_string(),
-                iter.next().unwrap().to_string(),
-                iter.next().unwrap().to_string(),
-            )
+impl FromStr for Job {
+    type Err = String;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = str.parse() {
+            return Ok(Job::Number(value));
+        }
+        match str.split(' ').collect::<Vec<&str>>()[..] {
+            [lhs, op @ ("+" | "-" | "*" | "/"), rhs] => Ok(Job::Operator(
+                lhs.to_string(),
+                op.to_string(),
+                rhs.to_string(),
+            )),
+            _ => Err(format!("expected a number or an operation, got {:?}", str)),
         }
     }
 }
@@ -34,7 +37,7 @@ impl Job {
 
     fn op(&self) -> &str {
         if let Self::Operator(_, op, _) = self {
-            &op
+            op
         } else {
             panic!("Unable to find job operator!")
         }
@@ -42,7 +45,7 @@ impl Job {
 }
 
 #[derive(Debug)]
-struct Monkey {
+pub struct Monkey {
     name: String,
     job: Job,
 }
@@ -84,10 +87,8 @@ impl Monkey {
             Job::Operator(lhs, _, rhs) => {
                 if let Some(l) = lookup.get(lhs).unwrap().get_path(lookup, path.to_vec()) {
                     Some(l)
-                } else if let Some(r) = lookup.get(rhs).unwrap().get_path(lookup, path.to_vec()) {
-                    Some(r)
                 } else {
-                    None
+                    lookup.get(rhs).unwrap().get_path(lookup, path.to_vec())
                 }
             }
             Job::Number(_) => None,
@@ -96,29 +97,58 @@ impl Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (left, right) = str.split_once(": ").unwrap();
+        let (left, right) = str.split_once(": ").ok_or("expected 'name: job'")?;
         Ok(Self {
             name: left.to_string(),
-            job: right.into(),
+            job: right.parse()?,
         })
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 21\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Monkey>;
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Monkey Math";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        let lines = input.clone().lines();
+        let names = lines
+            .iter()
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, _)| name)
+            .collect::<HashSet<&str>>();
+        let known = |name: &str| match names.contains(name) {
+            true => Ok(()),
+            false => Err(format!("no monkey named {:?}", name)),
+        };
+        let monkeys = input.clone().try_lines_with_all(|line| {
+            let monkey = line.parse::<Monkey>()?;
+            if let Some((lhs, rhs)) = monkey.job.get_children() {
+                known(&lhs)?;
+                known(&rhs)?;
+            }
+            Ok::<Monkey, String>(monkey)
+        })?;
+        for name in ["root", "humn"] {
+            known(name).map_err(|reason| input.error_at_end::<Monkey>(reason))?;
+        }
+        Ok(monkeys)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
 
-fn input() -> Vec<Monkey> {
-    reader::open("files/day21.txt").lines_as()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(values: Vec<Monkey>) -> isize {
+pub fn part_one(values: Vec<Monkey>) -> isize {
     let lookup = values
         .into_iter()
         .map(|monkey| (monkey.name.to_string(), monkey))
@@ -130,7 +160,7 @@ fn part_one(values: Vec<Monkey>) -> isize {
         .eval(&lookup, &mut HashMap::new())
 }
 
-fn part_two(values: Vec<Monkey>) -> isize {
+pub fn part_two(values: Vec<Monkey>) -> isize {
     let lookup = values
         .into_iter()
         .map(|monkey| (monkey.name.to_string(), monkey))
@@ -144,7 +174,7 @@ fn part_two(values: Vec<Monkey>) -> isize {
 
     let mut values = HashMap::new();
     lookup.get("root").unwrap().eval(&lookup, &mut values);
-    resolve("root".to_string(), 0, &path_to_humn, &lookup, &values) as isize
+    resolve("root".to_string(), 0, &path_to_humn, &lookup, &values)
 }
 
 fn resolve(
@@ -209,3 +239,14 @@ fn test_part_two() {
 fn get_test_input() -> Vec<Monkey> {
     reader::open("files/day21_test.txt").lines_as()
 }
+
+#[test]
+fn test_parse() {
+    let error = Day21::parse(reader::Reader::from("root: aaaa + bbbb\naaaa: 1\nhumn: 2"));
+    assert!(error.unwrap_err().to_string().starts_with("<input>:1: "));
+    let error = Day21::parse(reader::Reader::from("root: 1"));
+    assert!(error
+        .unwrap_err()
+        .to_string()
+        .ends_with("no monkey named \"humn\""));
+}
@@ -1,8 +1,13 @@
 use std::collections::HashMap;
+use std::str::FromStr;
 
+use crate::error::Error;
+use crate::geometry::{Direction, Point2};
+use crate::grid::Grid;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 
-type Point = (isize, isize);
+type Point = Point2<isize>;
 
 #[derive(Debug)]
 enum Move {
@@ -12,171 +17,156 @@ enum Move {
 }
 
 #[derive(Debug)]
-struct Map {
-    map: Vec<Vec<char>>,
+pub struct Input {
+    map: Grid<char>,
+    moves: Vec<Move>,
 }
 
-impl From<&str> for Map {
-    fn from(input: &str) -> Self {
-        let rows = input
-            .lines()
-            .map(|line| line.chars().into_iter().collect())
-            .collect::<Vec<Vec<char>>>();
-        let max = rows.iter().map(|row| row.len()).max().unwrap();
-
-        Self {
-            map: rows
-                .into_iter()
-                .map(|row| {
-                    let len = row.len();
-                    row.into_iter()
-                        .chain((0..(max - len)).map(|_| ' '))
-                        .collect()
-                })
-                .collect(),
+impl TryFrom<reader::Reader> for Input {
+    type Error = Error;
+
+    fn try_from(input: reader::Reader) -> Result<Self, Self::Error> {
+        let mut blocks = input.blocks().into_iter();
+        let map = blocks.next().map(|map| map.text()).unwrap_or_default();
+        let mut moves = Vec::new();
+        for block in blocks {
+            moves.extend(
+                block
+                    .try_lines_as_all::<Moves>()?
+                    .into_iter()
+                    .flat_map(|m| m.0),
+            );
         }
+        Ok(Self {
+            map: Grid::parse_padded(&map, ' ', |_, char| char),
+            moves,
+        })
     }
 }
 
-#[derive(Debug)]
-struct Input {
-    map: Map,
-    moves: Vec<Move>,
-}
+/// Line of the path to follow, numbers of tiles to move forward separated by
+/// turns.
+struct Moves(Vec<Move>);
 
-impl From<String> for Input {
-    fn from(input: String) -> Self {
-        let (map, moves) = input.split_once("\n\n").unwrap();
-        Self {
-            map: map.into(),
-            moves: Self::parse_moves(moves),
-        }
-    }
-}
+impl FromStr for Moves {
+    type Err = String;
 
-impl Input {
-    fn parse_moves(str: &str) -> Vec<Move> {
-        let mut char_iter = str.chars().into_iter();
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let forward = |steps: &str| {
+            steps
+                .parse()
+                .map(Move::F)
+                .map_err(|error| format!("invalid number of tiles {:?}: {}", steps, error))
+        };
         let mut result = Vec::new();
         let mut start = 0;
         let mut end = 0;
-        while let Some(next) = char_iter.next() {
+        for next in str.chars() {
             match next {
                 '0'..='9' => end += 1,
                 'R' => {
-                    result.push(Move::F(str[start..end].parse().unwrap()));
+                    result.push(forward(&str[start..end])?);
                     result.push(Move::R);
                     end += 1;
                     start = end;
                 }
                 'L' => {
-                    result.push(Move::F(str[start..end].parse().unwrap()));
+                    result.push(forward(&str[start..end])?);
                     result.push(Move::L);
                     end += 1;
                     start = end;
                 }
-                _ => panic!("Unkown charachter: {:?}", next),
+                _ => return Err(format!("unknown character {:?}", next)),
             }
         }
-        result.push(Move::F(str[start..end].parse().unwrap()));
-        result
+        result.push(forward(&str[start..end])?);
+        Ok(Self(result))
     }
+}
 
-    fn tile(&self, x: isize, y: isize) -> char {
-        self.map.map[y as usize][x as usize]
+impl Input {
+    fn tile(&self, point: Point) -> char {
+        self.map[point.try_cast().unwrap().into()]
     }
 
-    fn is_side(&self, (x, y): Point) -> bool {
-        self.tile(x, y) != ' '
+    fn is_side(&self, point: Point) -> bool {
+        self.tile(point) != ' '
     }
 
     fn width(&self) -> isize {
-        self.map.map[0].len() as isize
+        self.map.width() as isize
     }
 
     fn height(&self) -> isize {
-        self.map.map.len() as isize
+        self.map.height() as isize
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 22\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Input;
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Monkey Map";
 
-fn input() -> Input {
-    reader::open("files/day22.txt").text().into()
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        input.try_into()
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(values: Input) -> isize {
-    let mut dir = (1, 0);
-    let mut pos = (
-        values.map.map[0]
-            .iter()
-            .enumerate()
-            .skip_while(|(_, n)| n != &&'.')
-            .next()
-            .unwrap()
-            .0 as isize,
-        0,
-    );
+pub fn part_one(values: Input) -> isize {
+    let mut dir = Direction::Right;
+    let start = values.map.find(|tile| *tile == '.').unwrap();
+    let mut pos = Point2::from(start).map(|n| n as isize);
 
     for rule in &values.moves {
         match rule {
             Move::F(n) => {
                 for _ in 0..*n {
-                    let (mut x, mut y) = next_in_bounds(
-                        (pos.0 + dir.0, pos.1 + dir.1),
-                        (values.width(), values.height()),
-                    );
-
-                    let mut next_tile = values.tile(x, y);
-                    while next_tile == ' ' {
-                        (x, y) = next_in_bounds(
-                            (x + dir.0, y + dir.1),
-                            (values.width(), values.height()),
-                        );
-                        next_tile = values.tile(x, y);
+                    let bounds = Point::new(values.width(), values.height());
+                    let mut next = next_in_bounds(pos + dir.offset(), bounds);
+                    while values.tile(next) == ' ' {
+                        next = next_in_bounds(next + dir.offset(), bounds);
                     }
 
-                    if next_tile == '.' {
-                        pos = (x, y);
+                    if values.tile(next) == '.' {
+                        pos = next;
                     } else {
                         break;
                     }
                 }
             }
-            Move::L => dir = (dir.1, -dir.0),
-            Move::R => dir = (-dir.1, dir.0),
+            Move::L => dir = dir.turn_left(),
+            Move::R => dir = dir.turn_right(),
         }
     }
-    (1000 * (pos.1 + 1)) + (4 * (pos.0 + 1)) + dir_score(dir)
+    password(pos, dir)
 }
 
-fn dir_score(dir: (isize, isize)) -> isize {
-    match dir {
-        (1, 0) => 0,
-        (0, 1) => 1,
-        (-1, 0) => 2,
-        (0, -1) => 3,
-        _ => panic!("Unkown direction: {:?}", dir),
-    }
+/// Final password, the facing is scored in the clockwise order of `Direction`.
+fn password(pos: Point, dir: Direction) -> isize {
+    (1000 * (pos.y + 1)) + (4 * (pos.x + 1)) + (dir as isize)
 }
 
-fn part_two(values: Input) -> isize {
+pub fn part_two(values: Input) -> isize {
     hard_two(values)
 }
 
 #[allow(dead_code)]
 fn print_minimap(values: Input) {
-    let mut label_iter = ('A'..).into_iter();
+    let mut label_iter = 'A'..;
     let size = (values.width() - values.height()).abs();
     for y in 0..(values.height() / size) {
         for x in 0..(values.width() / size) {
-            if values.is_side((x * size, y * size)) {
+            if values.is_side(Point::new(x * size, y * size)) {
                 print!("{}", label_iter.next().unwrap());
             } else {
                 print!(" ");
@@ -186,19 +176,9 @@ fn print_minimap(values: Input) {
     }
 }
 
-fn next_in_bounds((mut x, mut y): Point, (max_x, max_y): Point) -> Point {
-    if x < 0 {
-        x = max_x - 1;
-    } else if x >= max_x {
-        x = 0;
-    }
-
-    if y < 0 {
-        y = max_y - 1;
-    } else if y >= max_y {
-        y = 0;
-    }
-    (x, y)
+/// Wrap a point that stepped off the map around to the opposite side.
+fn next_in_bounds(point: Point, bounds: Point) -> Point {
+    Point::new(point.x.rem_euclid(bounds.x), point.y.rem_euclid(bounds.y))
 }
 
 const SIZE: isize = 50;
@@ -213,21 +193,20 @@ fn hard_two(values: Input) -> isize {
     mapping.insert('E', vec![('B', 2), ('F', 1), ('D', 0), ('C', 0)]);
     mapping.insert('F', vec![('E', 3), ('B', 0), ('A', 3), ('D', 0)]);
 
-    let mut pos = (0, 0);
+    let mut pos = Point::new(0, 0);
     let mut block = 'A';
-    let mut dir = 0;
+    let mut dir = Direction::Right;
 
     for rule in &values.moves {
         match rule {
             Move::F(n) => {
                 for _ in 0..*n {
                     let (next_pos, next_dir, next_block) =
-                        step(pos, dir, block, &mapping.get(&block).unwrap());
-                    let (gx, gy) = to_global(next_pos, next_block);
-                    let tile = values.tile(gx, gy);
+                        step(pos, dir, block, mapping.get(&block).unwrap());
+                    let tile = values.tile(to_global(next_pos, next_block));
 
                     assert_ne!(tile, ' ');
-                    if values.tile(gx, gy) == '.' {
+                    if tile == '.' {
                         pos = next_pos;
                         dir = next_dir;
                         block = next_block;
@@ -236,45 +215,34 @@ fn hard_two(values: Input) -> isize {
                     }
                 }
             }
-            Move::L => dir = (dir + 3) % 4,
-            Move::R => dir = (dir + 1) % 4,
+            Move::L => dir = dir.turn_left(),
+            Move::R => dir = dir.turn_right(),
         }
     }
 
-    pos = to_global(pos, block);
-    (1000 * (pos.1 + 1)) + (4 * (pos.0 + 1)) + (dir as isize)
+    password(to_global(pos, block), dir)
 }
 
 fn step(
     local_position: Point,
-    dir: usize,
+    dir: Direction,
     block: char,
-    rules: &Vec<(char, usize)>,
-) -> (Point, usize, char) {
+    rules: &[(char, usize)],
+) -> (Point, Direction, char) {
     let size = 50;
-    let mut new_position = local_position;
+    let mut new_position = local_position + dir.offset();
     let mut new_block = block;
     let mut new_dir = dir;
 
-    match dir {
-        0 => new_position.0 += 1,
-        1 => new_position.1 += 1,
-        2 => new_position.0 -= 1,
-        _ => new_position.1 -= 1,
-    }
-
     if should_wrap(new_position) {
-        let rule = &rules[dir];
+        let rule = &rules[dir as usize];
         new_block = rule.0;
 
-        new_position = (
-            (new_position.0 + size) % size,
-            (new_position.1 + size) % size,
-        );
+        new_position = new_position.map(|n| (n + size) % size);
 
         for _ in 0..rule.1 {
-            new_position = (size - new_position.1 - 1, new_position.0);
-            new_dir = (new_dir + 1) % 4;
+            new_position = Point::new(size - new_position.y - 1, new_position.x);
+            new_dir = new_dir.turn_right();
         }
     }
 
@@ -282,17 +250,17 @@ fn step(
 }
 
 fn should_wrap(local_position: Point) -> bool {
-    local_position.0 < 0 || local_position.1 < 0 || local_position.0 == 50 || local_position.1 == 50
+    local_position.x < 0 || local_position.y < 0 || local_position.x == 50 || local_position.y == 50
 }
 
 fn to_global(pos: Point, cos: char) -> Point {
     match cos {
-        'A' => (pos.0 + SIZE, pos.1),
-        'B' => (pos.0 + (2 * SIZE), pos.1),
-        'C' => (pos.0 + SIZE, pos.1 + SIZE),
-        'D' => (pos.0, pos.1 + (2 * SIZE)),
-        'E' => (pos.0 + SIZE, pos.1 + (2 * SIZE)),
-        'F' => (pos.0, pos.1 + (3 * SIZE)),
+        'A' => pos + Point::new(SIZE, 0),
+        'B' => pos + Point::new(2 * SIZE, 0),
+        'C' => pos + Point::new(SIZE, SIZE),
+        'D' => pos + Point::new(0, 2 * SIZE),
+        'E' => pos + Point::new(SIZE, 2 * SIZE),
+        'F' => pos + Point::new(0, 3 * SIZE),
         _ => panic!(),
     }
 }
@@ -304,10 +272,13 @@ fn test_part_one() {
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(input()), 147245);
+    assert_eq!(
+        part_two(reader::open("files/day22.txt").try_into().unwrap()),
+        147245
+    );
 }
 
 #[cfg(test)]
 fn get_test_input() -> Input {
-    reader::open("files/day22_test.txt").text().into()
+    reader::open("files/day22_test.txt").try_into().unwrap()
 }
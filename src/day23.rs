@@ -1,9 +1,13 @@
+use crate::error::Error;
+use crate::geometry::Point2;
+use crate::grid::Grid;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::collections::{HashMap, HashSet};
 
-type Point = (isize, isize);
+type Point = Point2<isize>;
 
-struct Board {
+pub struct Board {
     points: HashSet<Point>,
     rules: Vec<(Point, [Point; 3])>,
 }
@@ -11,24 +15,20 @@ struct Board {
 impl From<String> for Board {
     fn from(input: String) -> Self {
         Self {
-            points: input
-                .lines()
-                .enumerate()
-                .map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter(|(_, c)| *c == '#')
-                        .map(|(x, _)| (x as isize, y as isize))
-                        .collect::<Vec<Point>>()
-                })
-                .flatten()
+            points: Grid::parse_padded(&input, '.', |_, c| c)
+                .iter()
+                .filter(|(_, c)| **c == '#')
+                .map(|((x, y), _)| Point::new(x as isize, y as isize))
                 .collect::<HashSet<Point>>(),
-            rules: vec![
+            rules: [
                 ((0, -1), [(0, -1), (1, -1), (-1, -1)]),
                 ((0, 1), [(0, 1), (1, 1), (-1, 1)]),
                 ((-1, 0), [(-1, 0), (-1, 1), (-1, -1)]),
                 ((1, 0), [(1, 0), (1, 1), (1, -1)]),
-            ],
+            ]
+            .into_iter()
+            .map(|(offset, checked)| (offset.into(), checked.map(Point::from)))
+            .collect(),
         }
     }
 }
@@ -38,10 +38,10 @@ impl Board {
         let (mut min_x, mut max_x) = (isize::MAX, isize::MIN);
         let (mut min_y, mut max_y) = (isize::MAX, isize::MIN);
         for point in &self.points {
-            min_x = std::cmp::min(min_x, point.0);
-            max_x = std::cmp::max(max_x, point.0);
-            min_y = std::cmp::min(min_y, point.1);
-            max_y = std::cmp::max(max_y, point.1);
+            min_x = std::cmp::min(min_x, point.x);
+            max_x = std::cmp::max(max_x, point.x);
+            min_y = std::cmp::min(min_y, point.y);
+            max_y = std::cmp::max(max_y, point.y);
         }
         (min_x, min_y, max_x, max_y)
     }
@@ -62,70 +62,66 @@ impl Board {
 
     #[allow(dead_code)]
     fn print(&self) {
-        let (min_x, min_y, max_x, max_y) = self.bounds();
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                if self.points.contains(&(x, y)) {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            println!();
+        let (min_x, min_y, _, _) = self.bounds();
+        let mut grid = Grid::filled(self.width(), self.height(), '.');
+        for point in &self.points {
+            let position = (*point - Point::new(min_x, min_y)).try_cast().unwrap();
+            grid[position.into()] = '#';
         }
+        println!("{}", grid);
     }
 
     fn has_neighbour(&self, point: Point) -> bool {
-        for y in -1..=1 {
-            for x in -1..=1 {
-                if x == 0 && y == 0 {
-                    continue;
-                }
-
-                if self.points.contains(&(point.0 + x, point.1 + y)) {
-                    return true;
-                }
-            }
-        }
-        false
+        point
+            .neighbours8()
+            .iter()
+            .any(|neighbour| self.points.contains(neighbour))
     }
 
     fn get_proposition(&self, point: &Point, rule_offset: usize) -> Option<Point> {
         let rule_iter = self.rules.iter().cycle().skip(rule_offset).take(4);
         for (offset, rule) in rule_iter {
-            if rule.iter().any(|offset| {
-                self.points
-                    .contains(&(point.0 + offset.0, point.1 + offset.1))
-            }) {
+            if rule
+                .iter()
+                .any(|offset| self.points.contains(&(*point + *offset)))
+            {
                 continue;
             }
-            return Some((point.0 + offset.0, point.1 + offset.1));
+            return Some(*point + *offset);
         }
         None
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 23\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Board;
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "Unstable Diffusion";
 
-fn input() -> Board {
-    reader::open("files/day23.txt").text().into()
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.text().into())
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(mut board: Board) -> usize {
+pub fn part_one(mut board: Board) -> usize {
     for i in 0..10 {
         next(&mut board, i);
     }
     board.width() * board.height() - board.len()
 }
 
-fn part_two(mut board: Board) -> usize {
-    (0..).skip_while(|i| next(&mut board, *i)).next().unwrap() + 1
+pub fn part_two(mut board: Board) -> usize {
+    (0..).find(|i| !next(&mut board, *i)).unwrap() + 1
 }
 
 fn next(board: &mut Board, round: usize) -> bool {
@@ -162,7 +158,7 @@ fn next(board: &mut Board, round: usize) -> bool {
 
     assert_eq!(new_points.len(), board.points.len());
     board.points = new_points;
-    moving.len() > 0
+    !moving.is_empty()
 }
 
 #[test]
@@ -1,198 +1,164 @@
+use crate::cycle;
+use crate::error::Error;
+use crate::geometry::{Direction, Point2};
 use crate::reader;
-use std::collections::{HashSet, LinkedList};
+use crate::search::{self, Path};
+use crate::solution::{Answer, Solution};
+use std::collections::HashSet;
 
-type Point = (isize, isize);
+type Point = Point2<isize>;
+/// Position of the expedition and the minute of the storm cycle.
+type Node = (Point, usize);
 
 #[derive(Debug)]
-struct Board {
-    position: Point,
-    offset: Point,
+pub struct Board {
     start: Point,
     end: Point,
-    minutes: isize,
     width: isize,
     height: isize,
-    storms: Vec<(Point, char)>,
+    storms: Vec<(Point, Direction)>,
 }
 
-impl From<String> for Board {
-    fn from(value: String) -> Self {
-        let width = value.lines().next().unwrap().len() as isize - 2;
-        let height = value.lines().count() as isize - 2;
-        Self {
-            position: (0, -1),
-            start: (0, -1),
-            end: (width - 1, height),
-            offset: (0, 0),
-            minutes: 0,
+impl TryFrom<reader::Reader> for Board {
+    type Error = Error;
+
+    fn try_from(input: reader::Reader) -> Result<Self, Self::Error> {
+        let grid = input.try_grid(|_, chr| match chr {
+            '.' | '#' => Ok(None),
+            chr => Direction::try_from(chr).map(Some),
+        })?;
+        let width = grid.width() as isize - 2;
+        let height = grid.height() as isize - 2;
+        Ok(Self {
+            start: Point::new(0, -1),
+            end: Point::new(width - 1, height),
             width,
             height,
-            storms: value
-                .lines()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter(|(_, chr)| *chr != '.')
-                        .filter(|(_, chr)| *chr != '#')
-                        .map(|(x, chr)| ((x as isize - 1, y as isize - 1), chr))
-                        .collect::<Vec<(Point, char)>>()
+            storms: grid
+                .iter()
+                .filter_map(|((x, y), direction)| {
+                    let point = Point::new(x as isize - 1, y as isize - 1);
+                    direction.map(|direction| (point, direction))
                 })
                 .collect(),
-        }
+        })
     }
 }
 
 impl Board {
-    fn next(&self) -> Board {
-        Self {
-            position: self.position,
-            start: self.start,
-            end: self.end,
-            offset: (
-                (self.offset.0 + 1) % self.width,
-                (self.offset.1 + 1) % self.height,
-            ),
-            minutes: self.minutes + 1,
-            width: self.width,
-            height: self.height,
-            storms: self
-                .storms
-                .iter()
-                .map(|storm| self.next_storm(storm))
-                .collect(),
-        }
-    }
-
-    fn next_storm(&self, ((x, y), c): &(Point, char)) -> (Point, char) {
-        let mut res = (*x, *y);
-        match c {
-            '<' => res.0 = (res.0 + self.width - 1) % self.width,
-            '>' => res.0 = (res.0 + 1) % self.width,
-            '^' => res.1 = (res.1 + self.height - 1) % self.height,
-            'v' => res.1 = (res.1 + 1) % self.height,
-            _ => panic!("Unknown direction: '{:?}'", c),
-        }
-        (res, *c)
+    /// Positions of the storms after the given number of minutes.
+    fn storms_at(&self, minutes: isize) -> impl Iterator<Item = (Point, Direction)> + '_ {
+        self.storms.iter().map(move |(point, direction)| {
+            let next = *point + direction.offset() * minutes;
+            let next = Point::new(
+                next.x.rem_euclid(self.width),
+                next.y.rem_euclid(self.height),
+            );
+            (next, *direction)
+        })
     }
 
-    fn copy(&self) -> Self {
-        Self {
-            position: self.position,
-            start: self.start,
-            end: self.end,
-            offset: self.offset,
-            minutes: self.minutes,
-            width: self.width,
-            height: self.height,
-            storms: self.storms.to_vec(),
+    /// Occupied positions for every minute of the storm cycle, until every
+    /// storm is back in its initial place. Storms wrap around the board one
+    /// tile a minute, so they repeat with the minute modulo the width and the
+    /// height.
+    fn cycle(&self) -> Vec<HashSet<Point>> {
+        if self.width <= 0 || self.height <= 0 {
+            return vec![HashSet::new()];
         }
+        let (width, height) = (self.width as usize, self.height as usize);
+        let phase = |minutes: &usize| (minutes % width, minutes % height);
+        let history = cycle::hashed(0, width * height, |minutes| *minutes += 1, phase, |_| 0);
+        let period = history.cycle.map_or(1, |cycle| cycle.length) as isize;
+        (0..period)
+            .map(|minutes| self.storms_at(minutes).map(|(point, _)| point).collect())
+            .collect()
     }
 
-    fn is_free(&self, offset: Point) -> bool {
-        let position = (self.position.0 + offset.0, self.position.1 + offset.1);
-        let is_special = position == self.start || position == self.end; 
-        let in_x_range = (0..self.width).contains(&position.0);
-        let in_y_range = (0..self.height).contains(&position.1);
+    fn is_free(&self, position: Point, storms: &HashSet<Point>) -> bool {
+        let is_special = position == self.start || position == self.end;
+        let in_x_range = (0..self.width).contains(&position.x);
+        let in_y_range = (0..self.height).contains(&position.y);
         if (!in_x_range || !in_y_range) && !is_special {
             return false;
         }
 
-        self.storms.iter().all(|(point, _)| point != &position)
+        !storms.contains(&position)
     }
+}
 
-    #[allow(dead_code)]
-    fn get_point(&self, point: Point) -> char {
-        let mut points = Vec::new();
-        for storm in &self.storms {
-            if storm.0 == point {
-                points.push(storm.1);
-            }
-        }
-        match points.len() {
-            0 => '.',
-            1 => points[0],
-            _ => points.len().to_string().chars().next().unwrap(),
-        }
-    }
+pub struct Day24;
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.position == (x, y) {
-                    print!("E");
-                } else {
-                    print!("{}", self.get_point((x, y)));
-                }
-            }
-            println!("");
-        }
+impl Solution for Day24 {
+    type Input = Board;
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Blizzard Basin";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        input.try_into()
     }
-}
 
-pub fn run() {
-    println!(
-        "Day 24\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input)
+            .expect("No route through the blizzards")
+            .into()
+    }
 
-fn input() -> Board {
-    reader::open("files/day24.txt").text().into()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input)
+            .expect("No route through the blizzards")
+            .into()
+    }
 }
 
-fn part_one(board: Board) -> isize {
-    let end = board.end;
-    bfs(board, end).minutes
+/// Minutes to reach the goal, `None` if the blizzards always block the way.
+pub fn part_one(board: Board) -> Option<usize> {
+    let cycle = board.cycle();
+    Some(bfs(&board, &cycle, (board.start, 0), board.end)?.cost)
 }
 
-fn part_two(board: Board) -> isize {
-    let start = board.start;
-    let end = board.end;
-    bfs(bfs(bfs(board, end), start), end).minutes
+/// Minutes to reach the goal, go back for the snacks and reach the goal again.
+pub fn part_two(board: Board) -> Option<usize> {
+    let cycle = board.cycle();
+    let there = bfs(&board, &cycle, (board.start, 0), board.end)?;
+    let back = bfs(&board, &cycle, *there.nodes.last()?, board.start)?;
+    let again = bfs(&board, &cycle, *back.nodes.last()?, board.end)?;
+    Some(there.cost + back.cost + again.cost)
 }
 
-fn bfs(initial_board: Board, target: Point) -> Board {
-    let mut mem = HashSet::new();
-    let mut queue = LinkedList::new();
-    queue.push_back(initial_board);
-    while let Some(board) = queue.pop_front() {
-        if board.position == target {
-            return board;
-        }
-
-        let key = (board.position, board.offset);
-        if mem.contains(&key) {
-            continue;
-        }
-        mem.insert(key);
-
-        let next_board = board.next();
-        for offset in [(0, 0), (1, 0), (-1, 0), (0, -1), (0, 1)] {
-            if next_board.is_free(offset) {
-                let mut target_board = next_board.copy();
-                target_board.position.0 += offset.0;
-                target_board.position.1 += offset.1;
-                queue.push_back(target_board);
-            }
-        }
-    }
-    panic!("Unable to find path!");
+/// Quickest route from the start node to the target, waiting or moving
+/// every minute.
+fn bfs(
+    board: &Board,
+    cycle: &[HashSet<Point>],
+    start: Node,
+    target: Point,
+) -> Option<Path<Node, usize>> {
+    let moves = |(position, minute): &Node| {
+        let next_minute = (minute + 1) % cycle.len();
+        let steps = Direction::ALL.map(|direction| direction.offset());
+        [Point::default()]
+            .into_iter()
+            .chain(steps)
+            .map(|offset| *position + offset)
+            .filter(|next| board.is_free(*next, &cycle[next_minute]))
+            .map(|next| (next, next_minute))
+            .collect::<Vec<Node>>()
+    };
+    search::bfs(start, moves, |(position, _)| *position == target)
 }
 
 #[test]
 fn test_part_one() {
-    assert_eq!(part_one(get_test_input()), 18);
+    assert_eq!(part_one(get_test_input()), Some(18));
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(part_two(get_test_input()), 54);
+    assert_eq!(part_two(get_test_input()), Some(54));
 }
 
 #[cfg(test)]
 fn get_test_input() -> Board {
-    reader::open("files/day24_test.txt").text().into()
+    reader::open("files/day24_test.txt").try_into().unwrap()
 }
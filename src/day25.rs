@@ -1,18 +1,53 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
+use std::str::FromStr;
 
-pub fn run() {
-    println!("Day 25\n\tPart 1: {:?}", part_one(input()));
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<String>;
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Full of Hot Air";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        let numbers = input.try_lines_as_all::<Snafu>()?;
+        Ok(numbers.into_iter().map(|number| number.0).collect())
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(_: Self::Input) -> Answer {
+        Answer::None
+    }
 }
 
-fn input() -> Vec<String> {
-    reader::open("files/day25.txt").lines()
+/// Line of the input, a number in SNAFU notation.
+struct Snafu(String);
+
+impl FromStr for Snafu {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str.chars().find(|c| !"=-012".contains(*c)) {
+            _ if str.is_empty() => Err("expected a SNAFU number".to_string()),
+            Some(c) => Err(format!(
+                "expected a SNAFU digit, one of '=-012', got {:?}",
+                c
+            )),
+            None => Ok(Self(str.to_string())),
+        }
+    }
 }
 
-fn part_one(values: Vec<String>) -> String {
+pub fn part_one(values: Vec<String>) -> String {
     to_base_5(values.into_iter().map(|line| from_base_5(&line)).sum())
 }
 
-fn to_base_5(mut dec: isize) -> String {
+/// Convert a number into a SNAFU string, e.g. `2022` into `1=11-2`.
+pub fn to_base_5(mut dec: isize) -> String {
     let mut chars = Vec::new();
     while dec > 0 {
         dec = match dec % 5 {
@@ -25,14 +60,15 @@ fn to_base_5(mut dec: isize) -> String {
     chars.into_iter().rev().collect()
 }
 
-fn from_base_5(line: &str) -> isize {
+/// Convert a SNAFU string into a number, e.g. `1=11-2` into `2022`.
+pub fn from_base_5(line: &str) -> isize {
     line.chars()
         .rev()
         .fold((0, 1), |(sum, i), cur| {
             (
                 match cur {
                     '=' => sum + i * -2,
-                    '-' => sum + i * -1,
+                    '-' => sum + -i,
                     n => sum + i * char_to_isize(n),
                 },
                 i * 5,
@@ -58,3 +94,9 @@ fn test_part_one() {
 fn get_test_input() -> Vec<String> {
     reader::open("files/day25_test.txt").lines()
 }
+
+#[test]
+fn test_parse() {
+    let error = Day25::parse(reader::Reader::from("1=\n1=X")).unwrap_err();
+    assert!(error.to_string().starts_with("<input>:2: "), "{}", error);
+}
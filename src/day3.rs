@@ -1,9 +1,11 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use core::panic;
 use std::str::FromStr;
 use std::string::ParseError;
 
-struct Rucksack {
+pub struct Rucksack {
     chars: Vec<char>,
 }
 
@@ -49,19 +51,27 @@ impl Rucksack {
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 3\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
 
-fn input() -> Vec<Rucksack> {
-    reader::open("files/day3.txt").lines_as()
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(rucksacks: Vec<Rucksack>) -> usize {
+pub fn part_one(rucksacks: Vec<Rucksack>) -> usize {
     rucksacks
         .iter()
         .map(|sack| sack.split_into(2))
@@ -70,7 +80,7 @@ fn part_one(rucksacks: Vec<Rucksack>) -> usize {
         .sum()
 }
 
-fn part_two(rucksacks: Vec<Rucksack>) -> usize {
+pub fn part_two(rucksacks: Vec<Rucksack>) -> usize {
     rucksacks
         .chunks(3)
         .map(|chunks| chunks.iter().map(|sack| sack.chars.to_owned()).collect())
@@ -1,78 +1,74 @@
+use crate::error::Error;
+use crate::interval::IntervalSet;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::str::FromStr;
-use std::string::ParseError;
 
-struct Range {
-    start: u32,
-    end: u32,
+/// Sections assigned to an elf, written as an inclusive range `start-end`.
+fn parse_range(str: &str) -> Result<IntervalSet, String> {
+    let (start, end) = str.split_once('-').ok_or("expected a range 'start-end'")?;
+    let start: i64 = start.parse().map_err(|error| format!("{}", error))?;
+    let end: i64 = end.parse().map_err(|error| format!("{}", error))?;
+    Ok(IntervalSet::from(start..end + 1))
 }
 
-impl From<&str> for Range {
-    fn from(input: &str) -> Self {
-        let (start, end) = input.split_once('-').unwrap();
-        Self {
-            start: start.parse().unwrap(),
-            end: end.parse().unwrap(),
-        }
-    }
-}
-
-impl Range {
-    fn is_complete_overlap(&self, other: &Range) -> bool {
-        self.start <= other.start && self.end >= other.end
-    }
-
-    fn has_some_overlap(&self, other: &Range) -> bool {
-        self.start <= other.end && self.end >= other.end
-    }
-}
-
-struct Section {
-    left: Range,
-    right: Range,
+pub struct Section {
+    left: IntervalSet,
+    right: IntervalSet,
 }
 
 impl FromStr for Section {
-    type Err = ParseError;
+    type Err = String;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (left, rigth) = str.split_once(',').unwrap();
+        let (left, rigth) = str
+            .split_once(',')
+            .ok_or("expected two ranges separated by ','")?;
         Ok(Self {
-            left: left.into(),
-            right: rigth.into(),
+            left: parse_range(left)?,
+            right: parse_range(rigth)?,
         })
     }
 }
 
 impl Section {
     fn has_complete_overlap(&self) -> bool {
-        self.left.is_complete_overlap(&self.right) || self.right.is_complete_overlap(&self.left)
+        let overlap = self.left.intersection(&self.right);
+        overlap == self.left || overlap == self.right
     }
 
     fn has_some_overlap(&self) -> bool {
-        self.left.has_some_overlap(&self.right) || self.right.has_some_overlap(&self.left)
+        !self.left.intersection(&self.right).is_empty()
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 4\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Section>;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
 
-fn input() -> Vec<Section> {
-    reader::open("files/day4.txt").lines_as()
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(sections: Vec<Section>) -> usize {
+pub fn part_one(sections: Vec<Section>) -> usize {
     sections
         .iter()
         .filter(|section| section.has_complete_overlap())
         .count()
 }
 
-fn part_two(sections: Vec<Section>) -> usize {
+pub fn part_two(sections: Vec<Section>) -> usize {
     sections
         .iter()
         .filter(|section| section.has_some_overlap())
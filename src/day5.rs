@@ -1,4 +1,8 @@
+use crate::error::Error;
+use crate::pattern;
 use crate::reader;
+use crate::solution::{Answer, Solution};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Move {
@@ -7,71 +11,85 @@ struct Move {
     target: usize,
 }
 
-impl From<&str> for Move {
-    fn from(input: &str) -> Self {
-        let mut parts = input
-            .split_whitespace()
-            .map(|part| part.parse())
-            .filter_map(|result| result.ok());
-        Self {
-            count: parts.next().unwrap(),
-            source: parts.next().unwrap() - 1,
-            target: parts.next().unwrap() - 1,
+impl FromStr for Move {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (count, source, target) = pattern::parse("move {} from {} to {}", str)?;
+        match (source, target) {
+            (0, _) | (_, 0) => Err("stacks are numbered from 1".to_string()),
+            (source, target) => Ok(Self {
+                count,
+                source: source - 1,
+                target: target - 1,
+            }),
         }
     }
 }
 
-impl Move {
-    fn parse_moves(moves: &str) -> Vec<Move> {
-        moves.lines().map(|line| line.into()).collect()
+/// Line of the drawing of the crates, with the crate on top of every stack
+/// at that height if there is one.
+struct Row(Vec<Option<char>>);
+
+impl FromStr for Row {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let chars = str.chars().collect::<Vec<char>>();
+        let cells = chars.chunks(4).map(|chunk| {
+            // Trailing spaces may be missing.
+            let char = |index: usize| chunk.get(index).copied().unwrap_or(' ');
+            match (char(0), char(1), char(2), char(3)) {
+                ('[', c, ']', ' ') => Ok(Some(c)),
+                // The line of stack numbers below the crates holds no crates.
+                (' ', c, ' ', ' ') if c == ' ' || c.is_ascii_digit() => Ok(None),
+                _ => Err(format!(
+                    "expected a crate, got {:?}",
+                    String::from_iter(chunk)
+                )),
+            }
+        });
+        Ok(Self(cells.collect::<Result<_, _>>()?))
     }
 }
 
 #[derive(Debug)]
-struct Crane {
+pub struct Crane {
     crates: Vec<Vec<char>>,
     moves: Vec<Move>,
 }
 
-impl From<String> for Crane {
-    fn from(input: String) -> Self {
-        let (crates, moves) = input.split_once("\n\n").unwrap();
-        Self {
-            crates: Crane::parse_crates(crates),
-            moves: Move::parse_moves(moves),
+impl TryFrom<reader::Reader> for Crane {
+    type Error = Error;
+
+    fn try_from(input: reader::Reader) -> Result<Self, Self::Error> {
+        let mut blocks = input.blocks().into_iter();
+        let rows = match blocks.next() {
+            Some(crates) => crates.try_lines_as_all::<Row>()?,
+            None => Vec::new(),
+        };
+        let mut moves = Vec::new();
+        for block in blocks {
+            moves.extend(block.try_lines_as_all::<Move>()?);
         }
+        Ok(Self {
+            crates: Crane::stack(rows),
+            moves,
+        })
     }
 }
 
 impl Crane {
-    fn parse_crate_line(line: &str) -> Vec<Vec<char>> {
-        line.chars()
-            .collect::<Vec<char>>()
-            .chunks(4)
-            .map(|chunk| vec![chunk[1]])
-            .collect()
-    }
-
-    fn combine_rows(acc: Vec<Vec<char>>, cur: Vec<Vec<char>>) -> Vec<Vec<char>> {
-        acc.into_iter().zip(cur).map(Self::build_stack).collect()
-    }
-
-    fn build_stack((top, bottom): (Vec<char>, Vec<char>)) -> Vec<char> {
-        bottom
-            .into_iter()
-            .chain(top.into_iter())
-            .into_iter()
-            .filter(|char| char != &' ')
-            .collect()
-    }
-
-    fn parse_crates(input: &str) -> Vec<Vec<char>> {
-        input
-            .lines()
-            .filter(|line| line.contains('['))
-            .map(Self::parse_crate_line)
-            .reduce(Self::combine_rows)
-            .unwrap()
+    /// Stacks of crates from the bottom up, from the rows of the drawing.
+    fn stack(rows: Vec<Row>) -> Vec<Vec<char>> {
+        let width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+        let mut stacks = vec![Vec::new(); width];
+        for row in rows.into_iter().rev() {
+            for (stack, cell) in stacks.iter_mut().zip(row.0) {
+                stack.extend(cell);
+            }
+        }
+        stacks
     }
 
     fn crate_mover_9000(self) -> String {
@@ -104,23 +122,31 @@ impl Crane {
     }
 }
 
-pub fn run() {
-    println!(
-        "Day 5\n\tPart 1: {}\n\tPart 2: {}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Crane;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        input.try_into()
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
 
-fn input() -> Crane {
-    reader::open("files/day5.txt").text().into()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(crane: Crane) -> String {
+pub fn part_one(crane: Crane) -> String {
     crane.crate_mover_9000()
 }
 
-fn part_two(crane: Crane) -> String {
+pub fn part_two(crane: Crane) -> String {
     crane.crate_mover_9001()
 }
 
@@ -136,5 +162,5 @@ fn test_part_two() {
 
 #[cfg(test)]
 fn get_test_input() -> Crane {
-    reader::open("files/day5_test.txt").text().into()
+    reader::open("files/day5_test.txt").try_into().unwrap()
 }
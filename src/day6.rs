@@ -1,22 +1,32 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 
-pub fn run() {
-    println!(
-        "Day 6\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = String;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.text())
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
 
-fn input() -> String {
-    reader::open("files/day6.txt").text()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: String) -> usize {
+pub fn part_one(input: String) -> usize {
     solve(input, 4)
 }
 
-fn part_two(input: String) -> usize {
+pub fn part_two(input: String) -> usize {
     solve(input, 14)
 }
 
@@ -1,25 +1,63 @@
+use crate::error::Error;
 use crate::reader;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
+use std::str::FromStr;
 
-pub fn run() {
-    println!(
-        "Day 7\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
+/// Line of the terminal output, a command or an entry listed by `ls`.
+pub enum Line {
+    Cd(String),
+    Ls,
+    Dir(String),
+    File(usize),
 }
 
-fn input() -> Vec<String> {
-    reader::open("files/day7.txt").lines()
+impl FromStr for Line {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["$", "cd", dir] => Ok(Self::Cd(dir.to_string())),
+            ["$", "ls"] => Ok(Self::Ls),
+            ["$", ..] => Err("unknown command".to_string()),
+            ["dir", dir] => Ok(Self::Dir(dir.to_string())),
+            [size, _] => size
+                .parse()
+                .map(Self::File)
+                .map_err(|error| format!("invalid size {:?}: {}", size, error)),
+            _ => Err("expected a command, a directory or a file".to_string()),
+        }
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Line>;
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        Ok(input.try_lines_as_all()?)
+    }
+
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(commands: Vec<String>) -> usize {
-    parse_commands(commands).into_values()
+pub fn part_one(commands: Vec<Line>) -> usize {
+    parse_commands(commands)
+        .into_values()
         .filter(|size| size <= &100_000)
         .sum()
 }
 
-fn part_two(commands: Vec<String>) -> usize {
+pub fn part_two(commands: Vec<Line>) -> usize {
     let dir = parse_commands(commands);
     let required = 30_000_000 - (70_000_000 - *dir.get("/").unwrap());
     dir.into_values()
@@ -28,30 +66,24 @@ fn part_two(commands: Vec<String>) -> usize {
         .unwrap()
 }
 
-fn parse_commands(lines: Vec<String>) -> HashMap<String, usize> {
+fn parse_commands(lines: Vec<Line>) -> HashMap<String, usize> {
     lines
         .into_iter()
         .rev()
         .fold(
             (HashMap::new(), 0),
-            |(mut dir, mut size), command| match command.starts_with('$') {
-                true => match command[2..].split_once(' ') {
-                    Some((_, c)) => match c {
-                        ".." => (dir, size),
-                        c => {
-                            dir.insert(c.to_owned(), size);
-                            (dir, 0)
-                        }
-                    },
-                    None => (dir, size),
-                },
-                false => {
-                    size += match command.split_once(' ').unwrap() {
-                        ("dir", d) => *dir.get(d).unwrap(),
-                        (s, _) => s.parse().unwrap(),
-                    };
+            |(mut dir, mut size), line| match line {
+                Line::Cd(d) if d == ".." => (dir, size),
+                Line::Cd(d) => {
+                    dir.insert(d, size);
+                    (dir, 0)
+                }
+                Line::Ls => (dir, size),
+                Line::Dir(d) => {
+                    size += *dir.get(&d).unwrap();
                     (dir, size)
                 }
+                Line::File(s) => (dir, size + s),
             },
         )
         .0
@@ -68,6 +100,6 @@ fn test_part_two() {
 }
 
 #[cfg(test)]
-fn get_test_input() -> Vec<String> {
-    reader::open("files/day7_test.txt").lines()
+fn get_test_input() -> Vec<Line> {
+    reader::open("files/day7_test.txt").lines_as()
 }
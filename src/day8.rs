@@ -1,8 +1,9 @@
 use std::ops::Range;
 
+use crate::error::Error;
+use crate::grid::{Grid, Position};
 use crate::reader;
-
-type Point = (usize, usize);
+use crate::solution::{Answer, Solution};
 
 enum Visibility {
     Visible(usize),
@@ -31,7 +32,7 @@ enum Trace {
 }
 
 impl Trace {
-    fn get_points(&self) -> Vec<Point> {
+    fn get_points(&self) -> Vec<Position> {
         match self {
             Self::U(n, r) => (r.start..r.end).map(|i| (*n, i)).rev().collect(),
             Self::D(n, r) => ((r.start + 1)..r.end).map(|i| (*n, i)).collect(),
@@ -41,37 +42,33 @@ impl Trace {
     }
 }
 
-struct Forest {
-    trees: Vec<usize>,
-    width: usize,
-    heigth: usize,
+pub struct Forest {
+    trees: Grid<usize>,
 }
 
-impl From<String> for Forest {
-    fn from(input: String) -> Self {
-        Self {
-            width: input.lines().next().unwrap().len(),
-            heigth: input.lines().count(),
-            trees: input
-                .replace("\n", "")
-                .chars()
-                .map(|char| {
-                    println!("{:?} -> {}", char, char as usize);
-                    (char as usize) - ('0' as usize)
-                })
-                .collect(),
-        }
+impl TryFrom<reader::Reader> for Forest {
+    type Error = Error;
+
+    fn try_from(input: reader::Reader) -> Result<Self, Self::Error> {
+        let height = |_, char: char| {
+            char.to_digit(10)
+                .map(|n| n as usize)
+                .ok_or("expected a digit")
+        };
+        Ok(Self {
+            trees: input.try_grid(height)?,
+        })
     }
 }
 
 impl Forest {
-    fn is_visible(&self, point: Point) -> bool {
+    fn is_visible(&self, point: Position) -> bool {
         self.get_visibility(point)
             .into_iter()
             .any(|visibility| visibility.is_visible())
     }
 
-    fn scenic_score(&self, point: Point) -> usize {
+    fn scenic_score(&self, point: Position) -> usize {
         self.get_visibility(point)
             .into_iter()
             .map(|visibility| visibility.value())
@@ -82,59 +79,49 @@ impl Forest {
         let mut distance = 0;
         for point in trace.get_points() {
             distance += 1;
-            if self.get_value(point) >= &value {
+            if self.trees[point] >= value {
                 return Visibility::Blocked(distance);
             }
         }
         Visibility::Visible(distance)
     }
 
-    fn get_visibility(&self, (x, y): Point) -> Vec<Visibility> {
-        let current = *self.get_value((x, y));
+    fn get_visibility(&self, (x, y): Position) -> Vec<Visibility> {
+        let current = self.trees[(x, y)];
         vec![
             self.trace(current, Trace::L(y, 0..x)),
-            self.trace(current, Trace::R(y, x..self.width())),
+            self.trace(current, Trace::R(y, x..self.trees.width())),
             self.trace(current, Trace::U(x, 0..y)),
-            self.trace(current, Trace::D(x, y..self.heigth())),
+            self.trace(current, Trace::D(x, y..self.trees.height())),
         ]
     }
 
-    fn get_points(&self) -> Vec<Point> {
-        (0..self.trees.len())
-            .map(|i| self.index_to_point(i))
-            .collect()
+    fn get_points(&self) -> Vec<Position> {
+        self.trees.positions().collect()
     }
+}
 
-    fn get_value(&self, (x, y): Point) -> &usize {
-        &self.trees[x + (y * self.width())]
-    }
+pub struct Day8;
 
-    fn index_to_point(&self, i: usize) -> Point {
-        (i % self.width(), i / self.heigth())
-    }
+impl Solution for Day8 {
+    type Input = Forest;
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
 
-    fn width(&self) -> usize {
-        self.width
+    fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+        input.try_into()
     }
 
-    fn heigth(&self) -> usize {
-        self.heigth
+    fn part_one(input: Self::Input) -> Answer {
+        part_one(input).into()
     }
-}
-
-pub fn run() {
-    println!(
-        "Day 8\n\tPart 1: {:?}\n\tPart 2: {:?}",
-        part_one(input()),
-        part_two(input())
-    );
-}
 
-fn input() -> Forest {
-    reader::open("files/day8.txt").text().into()
+    fn part_two(input: Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(forest: Forest) -> usize {
+pub fn part_one(forest: Forest) -> usize {
     forest
         .get_points()
         .into_iter()
@@ -142,7 +129,7 @@ fn part_one(forest: Forest) -> usize {
         .count()
 }
 
-fn part_two(forest: Forest) -> usize {
+pub fn part_two(forest: Forest) -> usize {
     forest.get_points().into_iter().fold(0, |max, point| {
         std::cmp::max(max, forest.scenic_score(point))
     })
@@ -160,5 +147,5 @@ fn test_part_two() {
 
 #[cfg(test)]
 fn get_test_input() -> Forest {
-    reader::open("files/day8_test.txt").text().into()
+    reader::open("files/day8_test.txt").try_into().unwrap()
 }
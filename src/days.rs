@@ -0,0 +1,35 @@
+use crate::solution::Puzzle;
+
+/// Every registered day, in order. `new-day` adds new days here.
+pub const DAYS: &[&dyn Puzzle] = &[
+    &crate::day1::Day1,
+    &crate::day2::Day2,
+    &crate::day3::Day3,
+    &crate::day4::Day4,
+    &crate::day5::Day5,
+    &crate::day6::Day6,
+    &crate::day7::Day7,
+    &crate::day8::Day8,
+    &crate::day9::Day9,
+    &crate::day10::Day10,
+    &crate::day11::Day11,
+    &crate::day12::Day12,
+    &crate::day13::Day13,
+    &crate::day14::Day14,
+    &crate::day15::Day15,
+    &crate::day16::Day16,
+    &crate::day17::Day17,
+    &crate::day18::Day18,
+    &crate::day19::Day19,
+    &crate::day20::Day20,
+    &crate::day21::Day21,
+    &crate::day22::Day22,
+    &crate::day23::Day23,
+    &crate::day24::Day24,
+    &crate::day25::Day25,
+];
+
+/// Find the registered day with the given number.
+pub fn get(number: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().copied().find(|day| day.day() == number)
+}
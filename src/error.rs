@@ -0,0 +1,57 @@
+use std::fmt;
+
+/// Error raised while reading or parsing puzzle input.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io { path: String, error: std::io::Error },
+    /// Part of the input could not be parsed into the target type.
+    Parse {
+        /// Input file path, `None` for inputs not read from a file.
+        path: Option<String>,
+        /// 1-based line number where the offending text starts.
+        line: usize,
+        text: String,
+        type_name: &'static str,
+        reason: String,
+    },
+    /// Several parts of the input could not be parsed, see `Reader::try_lines_as_all`.
+    Multiple(Vec<Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "Unable to read '{}': {}", path, error),
+            Self::Parse {
+                path,
+                line,
+                text,
+                type_name,
+                reason,
+            } => {
+                write!(f, "{}:{}: ", path.as_deref().unwrap_or("<input>"), line)?;
+                write!(f, "unable to parse {:?} into \"{}\"", text, type_name)?;
+                match reason.is_empty() {
+                    true => Ok(()),
+                    false => write!(f, ": {}", reason),
+                }
+            }
+            Self::Multiple(errors) => {
+                let lines = errors.iter().map(|error| error.to_string());
+                write!(f, "{}", lines.collect::<Vec<String>>().join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Vec<Error>> for Error {
+    fn from(mut errors: Vec<Error>) -> Self {
+        match errors.len() {
+            1 => errors.remove(0),
+            _ => Self::Multiple(errors),
+        }
+    }
+}
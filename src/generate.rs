@@ -0,0 +1,1111 @@
+//! Random puzzle inputs in the format of every day. The inputs are built to be
+//! solvable, e.g. day 15 has a single spot left for the distress beacon and
+//! day 24 is checked to be crossable there and back. Numbers come from a seeded
+//! SplitMix64 generator rather than an external crate so the same seed always
+//! gives the same input.
+
+use crate::day25;
+use crate::geometry::{Direction, Point2};
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// Deterministic pseudo random numbers, SplitMix64. The same seed always
+/// produces the same sequence.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in the range, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        start + (self.next_u64() % (end - start + 1) as u64) as usize
+    }
+
+    /// Uniform number in the range, which must not be empty.
+    pub fn signed(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        start + (self.next_u64() % (end.abs_diff(start) + 1)) as i64
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < probability * (1u64 << 53) as f64
+    }
+
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+
+    /// Random word of lowercase letters with a length in the range.
+    fn word(&mut self, length: RangeInclusive<usize>) -> String {
+        (0..self.range(length))
+            .map(|_| (b'a' + self.range(0..=25) as u8) as char)
+            .collect()
+    }
+
+    /// Random word that is not in `used` yet, which it is added to.
+    fn unique_word(&mut self, length: RangeInclusive<usize>, used: &mut HashSet<String>) -> String {
+        loop {
+            let word = self.word(length.clone());
+            if used.insert(word.clone()) {
+                return word;
+            }
+        }
+    }
+}
+
+/// Random input generator of a day.
+struct Generator {
+    day: u8,
+    /// What `--size` counts.
+    size: &'static str,
+    /// Size of a real puzzle input.
+    default: usize,
+    sizes: RangeInclusive<usize>,
+    /// Size small enough for the tests to solve quickly without optimizations.
+    test_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+const GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        size: "the number of elves",
+        default: 250,
+        sizes: 1..=100_000,
+        test_size: 20,
+        generate: day1,
+    },
+    Generator {
+        day: 2,
+        size: "the number of rounds",
+        default: 2_500,
+        sizes: 1..=1_000_000,
+        test_size: 20,
+        generate: day2,
+    },
+    Generator {
+        day: 3,
+        size: "the number of rucksacks, rounded down to groups of 3",
+        default: 300,
+        sizes: 3..=1_000_000,
+        test_size: 30,
+        generate: day3,
+    },
+    Generator {
+        day: 4,
+        size: "the number of pairs",
+        default: 1_000,
+        sizes: 1..=1_000_000,
+        test_size: 20,
+        generate: day4,
+    },
+    Generator {
+        day: 5,
+        size: "the number of moves",
+        default: 500,
+        sizes: 1..=100_000,
+        test_size: 20,
+        generate: day5,
+    },
+    Generator {
+        day: 6,
+        size: "the length of the datastream",
+        default: 4_096,
+        sizes: 14..=1_000_000,
+        test_size: 40,
+        generate: day6,
+    },
+    Generator {
+        day: 7,
+        size: "the number of directories",
+        default: 180,
+        sizes: 1..=10_000,
+        test_size: 20,
+        generate: day7,
+    },
+    Generator {
+        day: 8,
+        size: "the width and height of the forest",
+        default: 99,
+        sizes: 1..=1_000,
+        test_size: 10,
+        generate: day8,
+    },
+    Generator {
+        day: 9,
+        size: "the number of motions",
+        default: 2_000,
+        sizes: 1..=100_000,
+        test_size: 20,
+        generate: day9,
+    },
+    Generator {
+        day: 10,
+        size: "the number of cycles",
+        default: 240,
+        sizes: 1..=100_000,
+        test_size: 240,
+        generate: day10,
+    },
+    Generator {
+        day: 11,
+        size: "the number of monkeys",
+        default: 8,
+        sizes: 2..=9,
+        test_size: 4,
+        generate: day11,
+    },
+    Generator {
+        day: 12,
+        size: "the width of the heightmap",
+        default: 160,
+        sizes: 26..=2_000,
+        test_size: 26,
+        generate: day12,
+    },
+    Generator {
+        day: 13,
+        size: "the number of packet pairs",
+        default: 150,
+        sizes: 1..=10_000,
+        test_size: 20,
+        generate: day13,
+    },
+    Generator {
+        day: 14,
+        size: "the number of rock paths",
+        default: 150,
+        sizes: 1..=10_000,
+        test_size: 20,
+        generate: day14,
+    },
+    Generator {
+        day: 15,
+        size: "the number of sensors",
+        default: 30,
+        sizes: 4..=1_000,
+        test_size: 4,
+        generate: day15,
+    },
+    Generator {
+        day: 16,
+        size: "the number of valves",
+        default: 60,
+        sizes: 2..=676,
+        test_size: 8,
+        generate: day16,
+    },
+    Generator {
+        day: 17,
+        size: "the number of jets",
+        default: 10_091,
+        sizes: 1..=100_000,
+        test_size: 40,
+        generate: day17,
+    },
+    Generator {
+        day: 18,
+        size: "the number of cubes",
+        default: 2_800,
+        sizes: 1..=10_000,
+        test_size: 50,
+        generate: day18,
+    },
+    Generator {
+        day: 19,
+        size: "the number of blueprints",
+        default: 30,
+        sizes: 1..=100,
+        test_size: 1,
+        generate: day19,
+    },
+    Generator {
+        day: 20,
+        size: "the number of numbers",
+        default: 5_000,
+        sizes: 2..=100_000,
+        test_size: 20,
+        generate: day20,
+    },
+    Generator {
+        day: 21,
+        size: "the approximate number of monkeys",
+        default: 2_000,
+        sizes: 2..=100_000,
+        test_size: 60,
+        generate: day21,
+    },
+    Generator {
+        day: 22,
+        size: "the number of steps in the path",
+        default: 2_000,
+        sizes: 1..=100_000,
+        test_size: 20,
+        generate: day22,
+    },
+    Generator {
+        day: 23,
+        size: "the width and height of the grove",
+        default: 72,
+        sizes: 1..=500,
+        test_size: 10,
+        generate: day23,
+    },
+    Generator {
+        day: 24,
+        size: "the width of the valley",
+        default: 150,
+        sizes: 2..=300,
+        test_size: 10,
+        generate: day24,
+    },
+    Generator {
+        day: 25,
+        size: "the number of fuel requirements",
+        default: 120,
+        sizes: 1..=10_000,
+        test_size: 20,
+        generate: day25,
+    },
+];
+
+/// Random input of a day in the format of the puzzle input, the same for
+/// the same seed and size. `None` uses the size of a real input.
+pub fn input(day: u8, seed: u64, size: Option<usize>) -> Result<String, String> {
+    let generator = generator(day)?;
+    let size = size.unwrap_or(generator.default);
+    if !generator.sizes.contains(&size) {
+        return Err(format!(
+            "Invalid size {} for day {}, expected {} from {} to {}.",
+            size,
+            day,
+            generator.size,
+            generator.sizes.start(),
+            generator.sizes.end()
+        ));
+    }
+    Ok((generator.generate)(&mut Rng::new(seed), size))
+}
+
+/// Size of the inputs of the given day used by the tests, small enough to
+/// solve quickly without optimizations.
+pub fn test_size(day: u8) -> Result<usize, String> {
+    Ok(generator(day)?.test_size)
+}
+
+fn generator(day: u8) -> Result<&'static Generator, String> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day == day)
+        .ok_or_else(|| format!("Day {} has no input generator.", day))
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().collect::<Vec<String>>().join("\n")
+}
+
+fn day1(rng: &mut Rng, elves: usize) -> String {
+    let elves = (0..elves).map(|_| {
+        let items = (0..rng.range(1..=15)).map(|_| rng.range(1_000..=20_000).to_string());
+        lines(items)
+    });
+    elves.collect::<Vec<String>>().join("\n\n")
+}
+
+fn day2(rng: &mut Rng, rounds: usize) -> String {
+    let rounds = (0..rounds).map(|_| {
+        let elf = rng.choose(&['A', 'B', 'C']);
+        format!("{} {}", elf, rng.choose(&['X', 'Y', 'Z']))
+    });
+    lines(rounds)
+}
+
+/// Every group of three shares only its badge, every rucksack has a single
+/// item type in both compartments.
+fn day3(rng: &mut Rng, rucksacks: usize) -> String {
+    let mut result = Vec::new();
+    for _ in 0..rucksacks / 3 {
+        let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<char>>();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        for pool in items.chunks(17) {
+            let (shared, rest) = pool.split_first().unwrap();
+            let (left, right) = rest.split_at(rng.range(1..=rest.len() - 1));
+            let half = rng.range(2..=16);
+            let badge_left = rng.chance(0.5);
+            let left = compartment(rng, *shared, badge_left.then_some(badge), left, half);
+            let right = compartment(rng, *shared, (!badge_left).then_some(badge), right, half);
+            result.push(left + &right);
+        }
+    }
+    lines(result)
+}
+
+fn compartment(
+    rng: &mut Rng,
+    shared: char,
+    badge: Option<char>,
+    others: &[char],
+    length: usize,
+) -> String {
+    let mut items = vec![shared];
+    items.extend(badge);
+    while items.len() < length {
+        items.push(rng.choose(others));
+    }
+    rng.shuffle(&mut items);
+    items.into_iter().collect()
+}
+
+fn day4(rng: &mut Rng, pairs: usize) -> String {
+    let sections = |rng: &mut Rng| {
+        let start = rng.range(1..=99);
+        format!("{}-{}", start, rng.range(start..=99.min(start + 40)))
+    };
+    lines((0..pairs).map(|_| format!("{},{}", sections(rng), sections(rng))))
+}
+
+/// Nine stacks, moves never take the last crate of a stack.
+fn day5(rng: &mut Rng, moves: usize) -> String {
+    let mut stacks = (0..9)
+        .map(|_| {
+            let crates = (0..rng.range(2..=8)).map(|_| (b'A' + rng.range(0..=25) as u8) as char);
+            crates.collect::<Vec<char>>()
+        })
+        .collect::<Vec<Vec<char>>>();
+
+    let highest = stacks.iter().map(Vec::len).max().unwrap();
+    let mut result = (0..highest)
+        .rev()
+        .map(|level| {
+            let row = stacks.iter().map(|stack| match stack.get(level) {
+                Some(item) => format!("[{}]", item),
+                None => "   ".to_string(),
+            });
+            row.collect::<Vec<String>>().join(" ")
+        })
+        .collect::<Vec<String>>();
+    let labels = (1..=stacks.len()).map(|n| format!(" {} ", n));
+    result.push(labels.collect::<Vec<String>>().join(" "));
+    result.push(String::new());
+
+    for _ in 0..moves {
+        let sources = (0..stacks.len())
+            .filter(|source| stacks[*source].len() > 1)
+            .collect::<Vec<usize>>();
+        let source = rng.choose(&sources);
+        let target = (source + rng.range(1..=stacks.len() - 1)) % stacks.len();
+        let count = rng.range(1..=stacks[source].len() - 1);
+        let split = stacks[source].len() - count;
+        let moved = stacks[source].split_off(split);
+        stacks[target].extend(moved);
+        result.push(format!(
+            "move {} from {} to {}",
+            count,
+            source + 1,
+            target + 1
+        ));
+    }
+    lines(result)
+}
+
+/// Letters from half of the alphabet up to a run of 14 distinct letters.
+fn day6(rng: &mut Rng, length: usize) -> String {
+    let mut letters = ('a'..='z').collect::<Vec<char>>();
+    rng.shuffle(&mut letters);
+    let marker = rng.range(0..=length - 14);
+    let mut datastream = (0..marker)
+        .map(|_| rng.choose(&letters[..13]))
+        .collect::<String>();
+    datastream.extend(&letters[..14]);
+    datastream.extend((marker + 14..length).map(|_| rng.choose(&letters)));
+    datastream
+}
+
+/// Directory names are unique, the files fill the disk with 41 to 69
+/// million so that a directory has to be deleted.
+fn day7(rng: &mut Rng, directories: usize) -> String {
+    let mut used = HashSet::from(["/".to_string()]);
+    let mut names = vec!["/".to_string()];
+    let mut children = vec![Vec::new(); directories];
+    for directory in 1..directories {
+        children[rng.range(0..=directory - 1)].push(directory);
+        names.push(rng.unique_word(2..=8, &mut used));
+    }
+
+    let mut files = (0..directories)
+        .map(|directory| {
+            let count = rng.range(usize::from(directory == 0)..=4);
+            let files = (0..count).map(|_| {
+                let name = match rng.chance(0.5) {
+                    true => format!("{}.{}", rng.word(1..=8), rng.word(1..=3)),
+                    false => rng.word(1..=8),
+                };
+                (name, rng.range(1_000..=300_000))
+            });
+            files.collect::<Vec<(String, usize)>>()
+        })
+        .collect::<Vec<Vec<(String, usize)>>>();
+    let total = files.iter().flatten().map(|(_, size)| size).sum::<usize>();
+    let disk = rng.range(41_000_000..=69_000_000);
+    for (_, size) in files.iter_mut().flatten() {
+        *size = (*size * disk / total).max(1);
+    }
+
+    let mut result = Vec::new();
+    session(rng, 0, &names, &children, &files, &mut result);
+    lines(result)
+}
+
+fn session(
+    rng: &mut Rng,
+    directory: usize,
+    names: &[String],
+    children: &[Vec<usize>],
+    files: &[Vec<(String, usize)>],
+    result: &mut Vec<String>,
+) {
+    result.push(format!("$ cd {}", names[directory]));
+    result.push("$ ls".to_string());
+    let mut entries = children[directory]
+        .iter()
+        .map(|child| format!("dir {}", names[*child]))
+        .chain(
+            files[directory]
+                .iter()
+                .map(|(name, size)| format!("{} {}", size, name)),
+        )
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut entries);
+    result.extend(entries);
+    for child in &children[directory] {
+        session(rng, *child, names, children, files, result);
+        result.push("$ cd ..".to_string());
+    }
+}
+
+fn day8(rng: &mut Rng, side: usize) -> String {
+    let rows = (0..side).map(|_| (0..side).map(|_| rng.choose(&DIGITS)).collect());
+    lines(rows)
+}
+
+const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+fn day9(rng: &mut Rng, motions: usize) -> String {
+    let motions = (0..motions).map(|_| {
+        let direction = rng.choose(&['R', 'D', 'L', 'U']);
+        format!("{} {}", direction, rng.range(1..=20))
+    });
+    lines(motions)
+}
+
+/// Keeps the register within the 40 pixels of a row, give or take a few.
+fn day10(rng: &mut Rng, cycles: usize) -> String {
+    let (mut x, mut cycle) = (1, 0);
+    let mut result = Vec::new();
+    while cycle < cycles {
+        if rng.chance(0.3) {
+            result.push("noop".to_string());
+            cycle += 1;
+        } else {
+            let mut value = rng.signed(1..=15) * rng.choose(&[-1, 1]);
+            if !(-5..=45).contains(&(x + value)) {
+                value = -value;
+            }
+            x += value;
+            result.push(format!("addx {}", value));
+            cycle += 2;
+        }
+    }
+    lines(result)
+}
+
+struct MonkeySpec {
+    items: Vec<usize>,
+    operator: char,
+    /// `None` for `old`.
+    operand: Option<usize>,
+    divisor: usize,
+    on_true: usize,
+    on_false: usize,
+}
+
+impl MonkeySpec {
+    fn operation(&self, old: usize) -> Option<usize> {
+        let operand = self.operand.unwrap_or(old);
+        match self.operator {
+            '*' => old.checked_mul(operand),
+            _ => old.checked_add(operand),
+        }
+    }
+}
+
+/// The divisors are distinct primes and a single monkey squares the worry
+/// level. Monkeys whose worry levels overflow in the 20 rounds of part one
+/// are thrown away and generated again, with fewer multiplications after a
+/// while and only additions in the end, which can not overflow.
+fn day11(rng: &mut Rng, monkeys: usize) -> String {
+    for attempt in 0.. {
+        let multiplying = match attempt {
+            0..=19 => 0.5,
+            20..=99 => 0.2,
+            _ => 0.0,
+        };
+        let mut divisors = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut divisors);
+        let squaring = (attempt < 20).then(|| rng.range(0..=monkeys - 1));
+        let specs = (0..monkeys)
+            .map(|monkey| {
+                let others = (0..monkeys)
+                    .filter(|other| *other != monkey)
+                    .collect::<Vec<usize>>();
+                let on_true = rng.choose(&others);
+                let mut on_false = rng.choose(&others);
+                while on_false == on_true && others.len() > 1 {
+                    on_false = rng.choose(&others);
+                }
+                let (operator, operand) = match rng.chance(multiplying) {
+                    _ if Some(monkey) == squaring => ('*', None),
+                    true => ('*', Some(rng.range(2..=19))),
+                    false => ('+', Some(rng.range(1..=8))),
+                };
+                MonkeySpec {
+                    items: (0..rng.range(1..=8)).map(|_| rng.range(50..=99)).collect(),
+                    operator,
+                    operand,
+                    divisor: divisors[monkey],
+                    on_true,
+                    on_false,
+                }
+            })
+            .collect::<Vec<MonkeySpec>>();
+        if fits_part_one(&specs) {
+            return specs
+                .iter()
+                .enumerate()
+                .map(monkey_block)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+        }
+    }
+    unreachable!()
+}
+
+fn fits_part_one(specs: &[MonkeySpec]) -> bool {
+    let mut items = specs
+        .iter()
+        .map(|spec| spec.items.clone())
+        .collect::<Vec<Vec<usize>>>();
+    for _ in 0..20 {
+        for (monkey, spec) in specs.iter().enumerate() {
+            for item in std::mem::take(&mut items[monkey]) {
+                let Some(worry) = spec.operation(item).map(|worry| worry / 3) else {
+                    return false;
+                };
+                let next = match worry % spec.divisor == 0 {
+                    true => spec.on_true,
+                    false => spec.on_false,
+                };
+                items[next].push(worry);
+            }
+        }
+    }
+    true
+}
+
+fn monkey_block((monkey, spec): (usize, &MonkeySpec)) -> String {
+    let items = spec
+        .items
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<String>>();
+    let operand = spec
+        .operand
+        .map_or("old".to_string(), |operand| operand.to_string());
+    [
+        format!("Monkey {}:", monkey),
+        format!("  Starting items: {}", items.join(", ")),
+        format!("  Operation: new = old {} {}", spec.operator, operand),
+        format!("  Test: divisible by {}", spec.divisor),
+        format!("    If true: throw to monkey {}", spec.on_true),
+        format!("    If false: throw to monkey {}", spec.on_false),
+    ]
+    .join("\n")
+}
+
+/// The ground rises by at most one level per column from `a` on the left
+/// to `z` on the right. It is only lowered off the row of the start and the
+/// last column, so the end can always be reached.
+fn day12(rng: &mut Rng, width: usize) -> String {
+    let height = (width / 4).max(2);
+    let (start, end) = (rng.range(0..=height - 1), rng.range(0..=height - 1));
+    let rows = (0..height).map(|y| {
+        let row = (0..width).map(|x| {
+            let mut level = 25 * x / (width - 1);
+            if y != start && x != width - 1 && rng.chance(0.3) {
+                level -= rng.range(0..=level);
+            }
+            match (x, y) {
+                (0, y) if y == start => 'S',
+                (x, y) if x == width - 1 && y == end => 'E',
+                _ => (b'a' + level as u8) as char,
+            }
+        });
+        row.collect::<String>()
+    });
+    lines(rows)
+}
+
+fn day13(rng: &mut Rng, pairs: usize) -> String {
+    let pairs = (0..pairs).map(|_| {
+        let left = packet(rng, 0);
+        let mut right = packet(rng, 0);
+        while right == left {
+            right = packet(rng, 0);
+        }
+        format!("{}\n{}", left, right)
+    });
+    pairs.collect::<Vec<String>>().join("\n\n")
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.range(0..=4)).map(|_| match depth < 4 && rng.chance(0.3) {
+        true => packet(rng, depth + 1),
+        false => rng.range(0..=10).to_string(),
+    });
+    format!("[{}]", items.collect::<Vec<String>>().join(","))
+}
+
+/// Horizontal and vertical segments below the source of the sand.
+fn day14(rng: &mut Rng, paths: usize) -> String {
+    let paths = (0..paths).map(|_| {
+        let (mut x, mut y) = (rng.signed(470..=530), rng.signed(15..=170));
+        let mut points = vec![format!("{},{}", x, y)];
+        let mut horizontal = rng.chance(0.5);
+        for _ in 0..rng.range(1..=5) {
+            let step = rng.signed(1..=8) * rng.choose(&[-1, 1]);
+            match horizontal {
+                true => x += step,
+                false => y = (y + step).max(2),
+            }
+            horizontal = !horizontal;
+            points.push(format!("{},{}", x, y));
+        }
+        points.join(" -> ")
+    });
+    lines(paths)
+}
+
+/// Four sensors diagonally around the distress beacon cover the rest of the
+/// search area of part two, the others stop short of the distress beacon.
+fn day15(rng: &mut Rng, sensors: usize) -> String {
+    const MAX: i64 = 4_000_000;
+    let distress = (rng.signed(0..=MAX), rng.signed(0..=MAX));
+    let mut result = Vec::new();
+    let mut sensor = |position: (i64, i64), beacon: (i64, i64)| {
+        result.push(format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            position.0, position.1, beacon.0, beacon.1
+        ));
+    };
+
+    for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        let offset = rng.signed(MAX / 2 + 1..=MAX * 5 / 8);
+        let position = (distress.0 + dx * offset, distress.1 + dy * offset);
+        sensor(position, (position.0 + dx * (2 * offset - 1), position.1));
+    }
+    for _ in 4..sensors {
+        let (position, distance) = loop {
+            let position = (rng.signed(0..=MAX), rng.signed(0..=MAX));
+            let distance = position.0.abs_diff(distress.0) + position.1.abs_diff(distress.1);
+            if distance > 1 {
+                break (position, distance as i64);
+            }
+        };
+        let radius = rng.signed(1..=distance - 1);
+        let dx = rng.signed(0..=radius);
+        let beacon = (
+            position.0 + dx * rng.choose(&[-1, 1]),
+            position.1 + (radius - dx) * rng.choose(&[-1, 1]),
+        );
+        sensor(position, beacon);
+    }
+    rng.shuffle(&mut result);
+    lines(result)
+}
+
+/// Connected tunnels with at most 15 valves releasing pressure, `AA` not
+/// being one of them.
+fn day16(rng: &mut Rng, valves: usize) -> String {
+    let mut names = ('A'..='Z')
+        .flat_map(|a| ('A'..='Z').map(move |b| format!("{}{}", a, b)))
+        .filter(|name| name != "AA")
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut names);
+    names.truncate(valves - 1);
+    names.insert(0, "AA".to_string());
+
+    let mut tunnels = vec![Vec::new(); valves];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..valves {
+        connect(valve, rng.range(0..=valve - 1));
+    }
+    for _ in 0..valves / 4 {
+        connect(rng.range(0..=valves - 1), rng.range(0..=valves - 1));
+    }
+
+    let mut rates = vec![0; valves];
+    let mut working = (1..valves).collect::<Vec<usize>>();
+    rng.shuffle(&mut working);
+    for valve in working.into_iter().take(15) {
+        rates[valve] = rng.range(2..=25);
+    }
+
+    let mut result = (0..valves)
+        .map(|valve| {
+            let targets = tunnels[valve]
+                .iter()
+                .map(|target| names[*target].as_str())
+                .collect::<Vec<&str>>();
+            let tunnels = match targets.len() {
+                1 => format!("tunnel leads to valve {}", targets[0]),
+                _ => format!("tunnels lead to valves {}", targets.join(", ")),
+            };
+            format!(
+                "Valve {} has flow rate={}; {}",
+                names[valve], rates[valve], tunnels
+            )
+        })
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut result);
+    lines(result)
+}
+
+fn day17(rng: &mut Rng, jets: usize) -> String {
+    (0..jets).map(|_| rng.choose(&['<', '>'])).collect()
+}
+
+fn day18(rng: &mut Rng, cubes: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+    while result.len() < cubes {
+        let cube = (rng.range(0..=21), rng.range(0..=21), rng.range(0..=21));
+        if seen.insert(cube) {
+            result.push(format!("{},{},{}", cube.0, cube.1, cube.2));
+        }
+    }
+    lines(result)
+}
+
+fn day19(rng: &mut Rng, blueprints: usize) -> String {
+    let blueprints = (1..=blueprints).map(|id| {
+        format!(
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            id,
+            rng.range(2..=4),
+            rng.range(2..=4),
+            rng.range(2..=4),
+            rng.range(4..=20),
+            rng.range(2..=4),
+            rng.range(7..=20)
+        )
+    });
+    lines(blueprints)
+}
+
+/// Numbers with a single zero.
+fn day20(rng: &mut Rng, numbers: usize) -> String {
+    let mut result = (1..numbers)
+        .map(|_| match rng.signed(-10_000..=9_999) {
+            n if n >= 0 => n + 1,
+            n => n,
+        })
+        .collect::<Vec<i64>>();
+    result.insert(rng.range(0..=numbers - 1), 0);
+    lines(result.iter().map(i64::to_string))
+}
+
+/// Monkeys of day 21 with unique names and the jobs they yell.
+struct Jobs {
+    used: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Jobs {
+    /// Monkey yelling exactly `target` after `budget` monkeys or so, dividing
+    /// only when the result is exact and yelling non-negative numbers.
+    fn expression(&mut self, rng: &mut Rng, target: i64, budget: usize) -> String {
+        let name = rng.unique_word(4..=4, &mut self.used);
+        if budget <= 1 && target >= 0 {
+            self.lines.push(format!("{}: {}", name, target));
+            return name;
+        }
+
+        let divisor = (2..=9).find(|k| target > 0 && target % k == 0);
+        let (lhs, operator, rhs) = match rng.range(0..=3) {
+            _ if target < 0 => {
+                let a = rng.signed(1..=100);
+                (a, '-', a - target)
+            }
+            0 if target > 1 => {
+                let a = rng.signed(1..=target - 1);
+                (a, '+', target - a)
+            }
+            1 if divisor.is_some() => (target / divisor.unwrap(), '*', divisor.unwrap()),
+            2 if target < 1_000_000_000 => {
+                let k = rng.signed(2..=9);
+                (target * k, '/', k)
+            }
+            _ => {
+                let a = rng.signed(1..=100);
+                (target + a, '-', a)
+            }
+        };
+        let half = budget.saturating_sub(1) / 2;
+        let lhs = self.expression(rng, lhs, half);
+        let rhs = self.expression(rng, rhs, budget.saturating_sub(1) - half);
+        self.lines
+            .push(format!("{}: {} {} {}", name, lhs, operator, rhs));
+        name
+    }
+}
+
+/// `humn` only takes part in additions, subtractions and multiplications,
+/// which part two can invert, its number is not the answer of part two.
+fn day21(rng: &mut Rng, monkeys: usize) -> String {
+    let mut jobs = Jobs {
+        used: HashSet::from(["root".to_string(), "humn".to_string()]),
+        lines: vec![format!("humn: {}", rng.signed(1..=5_000))],
+    };
+    let depth = (monkeys / 30).max(1);
+    let budget = monkeys.saturating_sub(2 * depth + 2) / (depth + 1);
+
+    let (mut name, mut value) = ("humn".to_string(), rng.signed(1..=5_000));
+    for _ in 0..depth {
+        let human_left = rng.chance(0.5);
+        let (operand, operator, result) = match rng.range(0..=2) {
+            0 if value.abs() < 100_000_000_000 => {
+                let k = rng.signed(2..=5);
+                (k, '*', value * k)
+            }
+            1 => {
+                let a = rng.signed(1..=1_000);
+                (a, '-', if human_left { value - a } else { a - value })
+            }
+            _ => {
+                let a = rng.signed(1..=1_000);
+                (a, '+', value + a)
+            }
+        };
+        let operand = jobs.expression(rng, operand, budget);
+        let parent = rng.unique_word(4..=4, &mut jobs.used);
+        jobs.lines.push(match human_left {
+            true => format!("{}: {} {} {}", parent, name, operator, operand),
+            false => format!("{}: {} {} {}", parent, operand, operator, name),
+        });
+        (name, value) = (parent, result);
+    }
+
+    let other = jobs.expression(rng, value, budget);
+    jobs.lines.push(match rng.chance(0.5) {
+        true => format!("root: {} + {}", name, other),
+        false => format!("root: {} + {}", other, name),
+    });
+    rng.shuffle(&mut jobs.lines);
+    lines(jobs.lines)
+}
+
+/// The cube net of the real input with faces of 50 tiles, part two only
+/// supports that one.
+fn day22(rng: &mut Rng, steps: usize) -> String {
+    const FACES: [(usize, usize); 6] = [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)];
+    let mut result = (0..200)
+        .map(|y| {
+            let width = FACES
+                .iter()
+                .filter(|face| face.1 == y / 50)
+                .map(|face| face.0 + 1);
+            let row =
+                (0..50 * width.max().unwrap()).map(|x| match FACES.contains(&(x / 50, y / 50)) {
+                    false => ' ',
+                    true if (x, y) == (50, 0) => '.',
+                    true => match rng.chance(0.1) {
+                        true => '#',
+                        false => '.',
+                    },
+                });
+            row.collect::<String>()
+        })
+        .collect::<Vec<String>>();
+    result.push(String::new());
+
+    let mut path = rng.range(1..=50).to_string();
+    for _ in 1..steps {
+        path.push(rng.choose(&['L', 'R']));
+        path += &rng.range(1..=50).to_string();
+    }
+    result.push(path);
+    lines(result)
+}
+
+fn day23(rng: &mut Rng, side: usize) -> String {
+    let rows = (0..side).map(|_| (0..side).map(|_| rng.choose(&['#', '.'])).collect());
+    lines(rows)
+}
+
+/// Blizzards cover three quarters of the valley, none of them move up or
+/// down in the columns of the entrance and the exit. Valleys that cannot be
+/// crossed there, back and there again are generated again.
+fn day24(rng: &mut Rng, width: usize) -> String {
+    let height = (width * 2 / 15).max(1);
+    loop {
+        let rows = (0..height)
+            .map(|_| {
+                let row = (0..width).map(|x| match rng.chance(0.75) {
+                    false => '.',
+                    true if x == 0 || x == width - 1 => rng.choose(&['<', '>']),
+                    true => rng.choose(&['<', '>', '^', 'v']),
+                });
+                row.collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        let valley = Valley::new(&rows);
+        let (start, end) = (Point2::new(0, -1), valley.size - Point2::new(1, 0));
+        let crossed = valley
+            .cross(start, end, 0)
+            .and_then(|minute| valley.cross(end, start, minute))
+            .and_then(|minute| valley.cross(start, end, minute));
+        if crossed.is_some() {
+            let mut result = vec![format!("#.{}", "#".repeat(width))];
+            result.extend(rows.iter().map(|row| format!("#{}#", row)));
+            result.push(format!("{}.#", "#".repeat(width)));
+            return lines(result);
+        }
+    }
+}
+
+struct Valley {
+    size: Point2<i64>,
+    blizzards: Vec<(Point2<i64>, Direction)>,
+}
+
+impl Valley {
+    fn new(rows: &[String]) -> Self {
+        let blizzards = rows.iter().enumerate().flat_map(|(y, row)| {
+            let tiles = row.chars().enumerate().filter(|(_, tile)| *tile != '.');
+            tiles.map(move |(x, tile)| {
+                let direction = Direction::try_from(tile).unwrap();
+                (Point2::new(x as i64, y as i64), direction)
+            })
+        });
+        Self {
+            size: Point2::new(rows[0].len() as i64, rows.len() as i64),
+            blizzards: blizzards.collect(),
+        }
+    }
+
+    /// First minute at which `to` can be reached when leaving `from` at
+    /// `minute`, `None` if it never can.
+    fn cross(&self, from: Point2<i64>, to: Point2<i64>, mut minute: i64) -> Option<i64> {
+        let mut reachable = HashSet::from([from]);
+        // The blizzards repeat, and so do the reachable tiles once they are
+        // the same as a multiple of the width and height earlier.
+        let mut seen = HashSet::new();
+        while !reachable.contains(&to) {
+            if minute % (self.size.x * self.size.y) == 0 {
+                let mut tiles = reachable.iter().copied().collect::<Vec<Point2<i64>>>();
+                tiles.sort();
+                if !seen.insert(tiles) {
+                    return None;
+                }
+            }
+            minute += 1;
+            let blizzards = self
+                .blizzards
+                .iter()
+                .map(|(position, direction)| {
+                    let next = *position + direction.offset() * minute;
+                    Point2::new(
+                        next.x.rem_euclid(self.size.x),
+                        next.y.rem_euclid(self.size.y),
+                    )
+                })
+                .collect::<HashSet<Point2<i64>>>();
+            reachable = reachable
+                .iter()
+                .flat_map(|tile| std::iter::once(*tile).chain(tile.neighbours4()))
+                .filter(|tile| {
+                    let inside =
+                        (0..self.size.x).contains(&tile.x) && (0..self.size.y).contains(&tile.y);
+                    (inside || *tile == from || *tile == to) && !blizzards.contains(tile)
+                })
+                .collect();
+        }
+        Some(minute)
+    }
+}
+
+fn day25(rng: &mut Rng, requirements: usize) -> String {
+    let requirements = (0..requirements).map(|_| {
+        let digits = rng.range(1..=20) as u32;
+        day25::to_base_5(rng.signed(1..=5i64.pow(digits)) as isize)
+    });
+    lines(requirements)
+}
+
+#[test]
+fn test_rng() {
+    let mut rng = Rng::new(7);
+    let numbers = (0..5).map(|_| rng.next_u64()).collect::<Vec<u64>>();
+    let mut again = Rng::new(7);
+    assert_eq!(
+        (0..5).map(|_| again.next_u64()).collect::<Vec<u64>>(),
+        numbers
+    );
+    assert!((0..1_000).all(|_| (3..=5).contains(&rng.range(3..=5))));
+    assert!((0..1_000).all(|_| (-2..=2).contains(&rng.signed(-2..=2))));
+
+    let mut items = (0..10).collect::<Vec<usize>>();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..10).collect::<Vec<usize>>());
+}
+
+#[test]
+fn test_input() {
+    assert_eq!(input(4, 1, Some(3)), input(4, 1, Some(3)));
+    assert_ne!(input(4, 1, Some(3)), input(4, 2, Some(3)));
+    assert_eq!(input(4, 1, Some(3)).unwrap().lines().count(), 3);
+    assert!(input(11, 1, Some(10)).is_err());
+    assert!(input(26, 1, None).is_err());
+}
@@ -0,0 +1,347 @@
+//! Points and vectors supporting the arithmetic operators and
+//! Manhattan/Chebyshev distances, with `Direction` for headings that turn.
+
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Point or vector in the plane. On maps `y` grows downwards, as in `Grid`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Point or vector in space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Point with `f` applied to both coordinates.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point2<U> {
+        Point2::new(f(self.x), f(self.y))
+    }
+
+    /// Point with both coordinates converted, `None` if either is out of
+    /// range of `U`, e.g. a negative coordinate as a grid position.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point2<U>> {
+        Some(Point2::new(
+            self.x.try_into().ok()?,
+            self.y.try_into().ok()?,
+        ))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Point with `f` applied to every coordinate.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point3<U> {
+        Point3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+/// Distances and neighbours, for the signed coordinate types.
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Point2<$t> {
+            pub fn manhattan(self, other: Self) -> $t {
+                (self.x - other.x).abs() + (self.y - other.y).abs()
+            }
+
+            /// Distance when diagonal steps are allowed.
+            pub fn chebyshev(self, other: Self) -> $t {
+                (self.x - other.x).abs().max((self.y - other.y).abs())
+            }
+
+            /// Vector with every coordinate replaced by its sign, a single
+            /// step towards the point in each axis.
+            pub fn signum(self) -> Self {
+                Self::new(self.x.signum(), self.y.signum())
+            }
+
+            /// The 4 horizontally and vertically adjacent points.
+            pub fn neighbours4(self) -> [Self; 4] {
+                Direction::ALL.map(|direction| self + direction.offset())
+            }
+
+            /// The 8 adjacent points, including the diagonal ones.
+            pub fn neighbours8(self) -> [Self; 8] {
+                [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)]
+                    .map(|offset| self + Self::from(offset))
+            }
+        }
+
+        impl Point3<$t> {
+            pub fn manhattan(self, other: Self) -> $t {
+                (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+            }
+
+            /// Distance when diagonal steps are allowed.
+            pub fn chebyshev(self, other: Self) -> $t {
+                let distance = (self.x - other.x).abs().max((self.y - other.y).abs());
+                distance.max((self.z - other.z).abs())
+            }
+
+            /// The 6 points sharing a face with the point.
+            pub fn neighbours6(self) -> [Self; 6] {
+                [(1, 0, 0), (0, 1, 0), (0, 0, 1), (-1, 0, 0), (0, -1, 0), (0, 0, -1)]
+                    .map(|offset| self + Self::from(offset))
+            }
+        }
+    )*};
+}
+
+impl_signed!(i32, i64, isize);
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// Scaling by a scalar.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// Scaling by a scalar.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+/// Point from its coordinates separated by commas, e.g. `1,2,3`.
+impl<T: FromStr> FromStr for Point3<T>
+where
+    T::Err: Display,
+{
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let coordinate = |part: &str| {
+            part.trim()
+                .parse()
+                .map_err(|error| format!("invalid coordinate {:?}: {}", part, error))
+        };
+        match str.split(',').collect::<Vec<&str>>()[..] {
+            [x, y, z] => Ok(Self::new(coordinate(x)?, coordinate(y)?, coordinate(z)?)),
+            _ => Err("expected a point 'x,y,z'".to_string()),
+        }
+    }
+}
+
+/// Direction on a map, in clockwise order starting with `Right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Self::Right, Self::Down, Self::Left, Self::Up];
+
+    /// Direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Unit vector of a step in the direction, `Up` decreasing `y`.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Up => (0, -1),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+
+    /// Arrow pointing in the direction, as drawn on puzzle maps.
+    pub fn arrow(self) -> char {
+        match self {
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+            Self::Up => '^',
+        }
+    }
+}
+
+/// Parses both the letters `R`, `D`, `L`, `U` and the arrows `>`, `v`, `<`, `^`.
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            'R' | '>' => Ok(Self::Right),
+            'D' | 'v' => Ok(Self::Down),
+            'L' | '<' => Ok(Self::Left),
+            'U' | '^' => Ok(Self::Up),
+            _ => Err(format!("unknown direction {:?}", char)),
+        }
+    }
+}
+
+#[test]
+fn test_point2() {
+    let a = Point2::<i32>::new(1, 2);
+    let b = Point2::new(4, -2);
+    assert_eq!(a + b, Point2::new(5, 0));
+    assert_eq!(a - b, Point2::new(-3, 4));
+    assert_eq!(-a * 2, Point2::new(-2, -4));
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!((b - a).signum(), Point2::new(1, -1));
+    assert_eq!(<(i32, i32)>::from(a), (1, 2));
+    assert_eq!(Point2::new(-1, 2).try_cast::<usize>(), None);
+    assert_eq!(a.try_cast::<usize>(), Some(Point2::new(1, 2)));
+    assert_eq!(a.neighbours8().len(), 8);
+}
+
+#[test]
+fn test_point3() {
+    let mut a = Point3::<i32>::new(1, 2, 3);
+    a += Point3::new(1, 1, -5);
+    assert_eq!(a, Point3::from((2, 3, -2)));
+    assert_eq!(a.manhattan(Point3::default()), 7);
+    assert_eq!(a.chebyshev(Point3::default()), 3);
+    assert!(a.neighbours6().iter().all(|n| n.manhattan(a) == 1));
+    assert_eq!("2,3,-2".parse(), Ok(a));
+    assert!("2,3".parse::<Point3<i32>>().is_err());
+}
+
+#[test]
+fn test_direction() {
+    let direction = Direction::try_from('^').unwrap();
+    assert_eq!(direction, Direction::Up);
+    assert_eq!(direction.turn_right(), Direction::Right);
+    assert_eq!(direction.turn_left(), Direction::Left);
+    assert_eq!(direction.reverse(), Direction::Down);
+    assert_eq!(direction.offset::<isize>(), Point2::new(0, -1));
+    assert!(Direction::try_from('x').is_err());
+}
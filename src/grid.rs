@@ -0,0 +1,308 @@
+//! Maps of characters parsed into a `Grid`, with bounds-checked access,
+//! 4/8-neighbours, rows and columns, rotations and `Display` for debug output.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Position of a cell as `(x, y)`, `(0, 0)` being the top left cell.
+pub type Position = (usize, usize);
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Grid of the given size from its cells in row order.
+    ///
+    /// Panics if the number of cells does not match the size.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "{}x{} grid with {} cells",
+            width,
+            height,
+            cells.len()
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Grid of the given size with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Grid with a cell per character of the text, mapped by `cell` from its
+    /// position and character. Every line must have the same length.
+    pub fn parse(text: &str, cell: impl FnMut(Position, char) -> T) -> Result<Self, String> {
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        if let Some((y, line)) = text
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.chars().count() != width)
+        {
+            return Err(format!(
+                "line {} has {} cells, expected {}",
+                y + 1,
+                line.chars().count(),
+                width
+            ));
+        }
+        Ok(Self::parse_padded(text, ' ', cell))
+    }
+
+    /// Like `parse`, lines shorter than the longest one are padded with the
+    /// `padding` character.
+    pub fn parse_padded(
+        text: &str,
+        padding: char,
+        mut cell: impl FnMut(Position, char) -> T,
+    ) -> Self {
+        let width = text.lines().map(|line| line.chars().count()).max();
+        let width = width.unwrap_or(0);
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (y, line) in text.lines().enumerate() {
+            let chars = line.chars().chain(std::iter::repeat(padding));
+            cells.extend(chars.take(width).enumerate().map(|(x, c)| cell((x, y), c)));
+            height += 1;
+        }
+        Self::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// Cell at a signed position, `None` when it is outside of the grid.
+    pub fn get_signed(&self, (x, y): (isize, isize)) -> Option<&T> {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => self.get((x, y)),
+            _ => None,
+        }
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.0 + position.1 * self.width)
+    }
+
+    /// Every position in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| (index % width, index / width))
+    }
+
+    /// Every cell with its position, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell in row order matching the predicate.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Positions of the up to 4 horizontally and vertically adjacent cells.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.adjacent(position, &[(0, -1), (1, 0), (0, 1), (-1, 0)])
+    }
+
+    /// Positions of the up to 8 adjacent cells, including the diagonal ones.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ];
+        self.adjacent(position, &OFFSETS)
+    }
+
+    fn adjacent<'a>(
+        &'a self,
+        (x, y): Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let position = (x.checked_add_signed(*dx)?, y.checked_add_signed(*dy)?);
+            self.contains(position).then_some(position)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, empty rows have no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} of a grid of width {}",
+            x,
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Grid of the same size with every cell mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Grid mirrored along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Grid rotated a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| self[(x, y)].clone()))
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Grid rotated a quarter turn counterclockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside of the {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside of the {}x{} grid", position, width, height),
+        }
+    }
+}
+
+/// Renders every row on its own line, cells without separators.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parse() {
+    let grid = Grid::parse("123\n456", |_, c| c.to_digit(10).unwrap()).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], 6);
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.get_signed((-1, 0)), None);
+    assert_eq!(grid.find(|cell| *cell == 5), Some((1, 1)));
+    assert_eq!(grid.to_string(), "123\n456");
+    assert!(Grid::parse("12\n3", |_, c| c).is_err());
+
+    let padded = Grid::parse_padded("ab\nc", '.', |_, c| c);
+    assert_eq!(padded.to_string(), "ab\nc.");
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = Grid::filled(3, 3, 0);
+    assert_eq!(
+        grid.neighbours4((0, 0)).collect::<Vec<Position>>(),
+        vec![(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+    assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+}
+
+#[test]
+fn test_transform() {
+    let grid = Grid::parse("ab\ncd\nef", |_, c| c).unwrap();
+    assert_eq!(grid.row(1), ['c', 'd']);
+    assert_eq!(grid.column(1).collect::<String>(), "bdf");
+    assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+    assert_eq!(grid.rotate_right().to_string(), "eca\nfdb");
+    assert_eq!(grid.rotate_left().to_string(), "bdf\nace");
+    assert_eq!(grid.rotate_right().rotate_left(), grid);
+    assert_eq!(
+        grid.map(|c| c.to_ascii_uppercase()).to_string(),
+        "AB\nCD\nEF"
+    );
+}
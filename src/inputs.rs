@@ -0,0 +1,224 @@
+use crate::answers::Answers;
+use crate::error::Error;
+use crate::reader::{self, Reader};
+use crate::solution::Puzzle;
+use std::path::Path;
+
+/// Input root used when neither `--input-dir` nor the environment variable is set.
+pub const DEFAULT_DIR: &str = "files";
+
+/// Environment variable overriding the input root.
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory holding the `dayN.txt` inputs and the `answers.toml` of one
+/// person, either the input root itself or one of its subdirectories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// Subdirectory name, `None` for the input root.
+    pub name: Option<String>,
+    pub dir: String,
+}
+
+impl Profile {
+    /// Name shown in reports, the directory for the input root.
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.dir)
+    }
+
+    /// Path of the input file of the given day.
+    pub fn input_path(&self, puzzle: &dyn Puzzle) -> String {
+        join(&self.dir, &puzzle.input_file())
+    }
+
+    /// Path of the answers file of the profile.
+    pub fn answers_path(&self) -> String {
+        join(&self.dir, "answers.toml")
+    }
+}
+
+/// Example input from a puzzle description, `dayN_test.txt` or `dayN_testK.txt`
+/// in the default input directory, with the expected answers of the examples.
+#[derive(Debug)]
+pub struct Example {
+    pub number: u8,
+    pub answers: Answers,
+}
+
+impl Example {
+    /// Load the expected answers of the examples with the given number.
+    pub fn load(number: u8) -> Result<Self, String> {
+        Ok(Self {
+            number,
+            answers: Answers::load(&Self::answers_path(number))?,
+        })
+    }
+
+    /// `examples.toml` for the first example of every day, `examplesK.toml`
+    /// for the others.
+    pub fn answers_path(number: u8) -> String {
+        match number {
+            1 => join(DEFAULT_DIR, "examples.toml"),
+            n => join(DEFAULT_DIR, &format!("examples{}.toml", n)),
+        }
+    }
+
+    /// Path of the example file of the given day, days with a single example
+    /// have no number in the file name.
+    pub fn input_path(&self, puzzle: &dyn Puzzle) -> String {
+        let numbered = format!("day{}_test{}.txt", puzzle.day(), self.number);
+        match self.number {
+            1 if !Path::new(&join(DEFAULT_DIR, &numbered)).exists() => {
+                join(DEFAULT_DIR, &format!("day{}_test.txt", puzzle.day()))
+            }
+            _ => join(DEFAULT_DIR, &numbered),
+        }
+    }
+}
+
+/// Where the puzzle inputs are read from.
+pub enum Source {
+    /// The input files of a profile.
+    Profile(Profile),
+    /// A single input given on the command line, used for every selected day.
+    Reader(Reader),
+    /// The example inputs of the puzzle descriptions.
+    Example(Example),
+}
+
+impl Source {
+    /// Read the input of the given day.
+    pub fn load(&self, puzzle: &dyn Puzzle) -> Result<Reader, Error> {
+        match self {
+            Self::Profile(profile) => reader::try_open(&profile.input_path(puzzle)),
+            Self::Reader(reader) => Ok(reader.clone()),
+            Self::Example(example) => reader::try_open(&example.input_path(puzzle)),
+        }
+    }
+
+    /// Parameter values of the given day that belong to the input, the
+    /// examples usually use smaller ones.
+    pub fn params(&self, puzzle: &dyn Puzzle) -> &[(String, i64)] {
+        match self {
+            Self::Example(example) => example.answers.params(puzzle.day()),
+            _ => &[],
+        }
+    }
+}
+
+/// Resolve the profiles to use.
+///
+/// The input root is `dir`, the `AOC_INPUT_DIR` environment variable or
+/// `files`, in that order. Without a `profile` the root itself is used,
+/// `all` selects the root and every subdirectory that contains input files.
+pub fn resolve(dir: Option<&str>, profile: Option<&str>) -> Result<Vec<Profile>, String> {
+    let root = match dir {
+        Some(dir) => dir.to_string(),
+        None => std::env::var(DIR_VAR).unwrap_or_else(|_| DEFAULT_DIR.to_string()),
+    };
+
+    match profile {
+        None => Ok(vec![Profile {
+            name: None,
+            dir: root,
+        }]),
+        Some("all") => discover(&root),
+        Some(name) => {
+            let dir = join(&root, name);
+            match Path::new(&dir).is_dir() {
+                true => Ok(vec![Profile {
+                    name: Some(name.to_string()),
+                    dir,
+                }]),
+                false => Err(format!("Profile directory '{}' does not exist.", dir)),
+            }
+        }
+    }
+}
+
+/// The root and every subdirectory of it that contains input files, sorted by name.
+fn discover(root: &str) -> Result<Vec<Profile>, String> {
+    let entries =
+        std::fs::read_dir(root).map_err(|error| format!("Unable to read '{}': {}", root, error))?;
+    let mut names = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir() && has_inputs(&entry.path()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect::<Vec<String>>();
+    names.sort();
+
+    let mut profiles = Vec::with_capacity(names.len() + 1);
+    if has_inputs(Path::new(root)) {
+        profiles.push(Profile {
+            name: None,
+            dir: root.to_string(),
+        });
+    }
+    profiles.extend(names.into_iter().map(|name| Profile {
+        dir: join(root, &name),
+        name: Some(name),
+    }));
+    Ok(profiles)
+}
+
+/// Whether the directory contains a `dayN.txt` file.
+fn has_inputs(dir: &Path) -> bool {
+    std::fs::read_dir(dir).map_or(false, |entries| {
+        entries.flatten().any(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.strip_prefix("day")
+                .and_then(|rest| rest.strip_suffix(".txt"))
+                .map_or(false, |n| n.parse::<u8>().is_ok())
+        })
+    })
+}
+
+fn join(dir: &str, file: &str) -> String {
+    Path::new(dir).join(file).to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_resolve() {
+    let root = std::env::temp_dir().join(format!("aoc_inputs_{}", std::process::id()));
+    for dir in ["alice", "bob", "notes"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    std::fs::write(root.join("day1.txt"), "").unwrap();
+    std::fs::write(root.join("alice").join("day12.txt"), "").unwrap();
+    std::fs::write(root.join("bob").join("day3.txt"), "").unwrap();
+    std::fs::write(root.join("notes").join("day3_test.txt"), "").unwrap();
+
+    let root_str = root.to_string_lossy().into_owned();
+    let names = resolve(Some(&root_str), Some("all"))
+        .unwrap()
+        .into_iter()
+        .map(|profile| profile.name)
+        .collect::<Vec<Option<String>>>();
+    assert_eq!(
+        names,
+        vec![None, Some("alice".to_string()), Some("bob".to_string())]
+    );
+
+    let alice = resolve(Some(&root_str), Some("alice")).unwrap();
+    assert_eq!(alice[0].dir, join(&root_str, "alice"));
+    assert!(resolve(Some(&root_str), Some("carol")).is_err());
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_example() {
+    let example = Example::load(1).unwrap();
+    assert_eq!(
+        example.input_path(&crate::day1::Day1),
+        join(DEFAULT_DIR, "day1_test.txt")
+    );
+    assert_eq!(
+        example.input_path(&crate::day9::Day9),
+        join(DEFAULT_DIR, "day9_test1.txt")
+    );
+    assert_eq!(
+        example.answers.get(5, 2),
+        Some(&crate::solution::Answer::Text("MCD".to_string()))
+    );
+    assert!(Example::load(2).unwrap().answers.get(9, 2).is_some());
+}
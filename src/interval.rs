@@ -0,0 +1,186 @@
+//! Ranges of integers, such as the sections of day 4 or the positions covered
+//! by the sensors of day 15, merged into an `IntervalSet` answering coverage,
+//! gap and containment queries.
+
+use std::ops::Range;
+
+/// Set of integers stored as sorted, disjoint and non-adjacent half-open
+/// intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every integer of the range, merging it with the intervals it
+    /// overlaps or touches.
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|interval| interval.end < range.start);
+        let last = self
+            .intervals
+            .partition_point(|interval| interval.start <= range.end);
+        let merged = match self.intervals.get(first..last) {
+            Some([head, .., tail]) | Some([head @ tail]) => {
+                head.start.min(range.start)..tail.end.max(range.end)
+            }
+            _ => range,
+        };
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Remove every integer of the range.
+    pub fn remove(&mut self, range: Range<i64>) {
+        *self = self.difference(&Self::from(range));
+    }
+
+    pub fn clear(&mut self) {
+        self.intervals.clear();
+    }
+
+    /// The merged intervals in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Range<i64>> {
+        self.intervals.iter()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals
+            .iter()
+            .map(|interval| interval.end - interval.start)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.contains_range(&(value..value + 1))
+    }
+
+    /// Whether every integer of the range is in the set.
+    pub fn contains_range(&self, range: &Range<i64>) -> bool {
+        range.is_empty()
+            || self
+                .intervals
+                .iter()
+                .any(|interval| interval.start <= range.start && range.end <= interval.end)
+    }
+
+    /// Whether any integer of the range is in the set.
+    pub fn overlaps(&self, range: &Range<i64>) -> bool {
+        self.intervals
+            .iter()
+            .any(|interval| interval.start < range.end && range.start < interval.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in &other.intervals {
+            result.insert(interval.clone());
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let (mut left, mut right) = (self.intervals.iter(), other.intervals.iter());
+        let (mut a, mut b) = (left.next(), right.next());
+        while let (Some(x), Some(y)) = (a, b) {
+            result.insert(x.start.max(y.start)..x.end.min(y.end));
+            if x.end < y.end {
+                a = left.next();
+            } else {
+                b = right.next();
+            }
+        }
+        result
+    }
+
+    /// The integers of the set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        for interval in &self.intervals {
+            for gap in other.gaps(interval.clone()) {
+                result.insert(gap);
+            }
+        }
+        result
+    }
+
+    /// The ranges of integers within `bounds` that are not in the set, in
+    /// ascending order.
+    pub fn gaps(&self, bounds: Range<i64>) -> impl Iterator<Item = Range<i64>> + '_ {
+        let mut start = bounds.start;
+        self.intervals
+            .iter()
+            .cloned()
+            .chain(std::iter::once(bounds.end..bounds.end))
+            .filter_map(move |interval| {
+                let gap = start..interval.start.min(bounds.end);
+                start = start.max(interval.end);
+                (!gap.is_empty()).then_some(gap)
+            })
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[test]
+fn test_insert() {
+    let mut set = IntervalSet::from_iter([5..8, 1..3, 10..12]);
+    assert_eq!(set.iter().count(), 3);
+    set.insert(3..5);
+    assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![1..8, 10..12]);
+    set.insert(0..20);
+    assert_eq!(set, IntervalSet::from(0..20));
+    set.insert(4..4);
+    assert_eq!(set.len(), 20);
+    set.remove(5..10);
+    assert_eq!(set, IntervalSet::from_iter([0..5, 10..20]));
+}
+
+#[test]
+fn test_queries() {
+    let set = IntervalSet::from_iter([1..3, 6..9]);
+    assert!(set.contains(2) && !set.contains(3));
+    assert!(set.contains_range(&(6..9)) && !set.contains_range(&(2..7)));
+    assert!(set.overlaps(&(2..7)) && !set.overlaps(&(3..6)));
+    assert_eq!(set.gaps(0..10).collect::<Vec<_>>(), vec![0..1, 3..6, 9..10]);
+    assert_eq!(set.gaps(7..8).count(), 0);
+}
+
+#[test]
+fn test_set_operations() {
+    let a = IntervalSet::from_iter([0..5, 10..15]);
+    let b = IntervalSet::from(3..12);
+    assert_eq!(a.union(&b), IntervalSet::from(0..15));
+    assert_eq!(a.intersection(&b), IntervalSet::from_iter([3..5, 10..12]));
+    assert_eq!(a.difference(&b), IntervalSet::from_iter([0..3, 12..15]));
+    assert_eq!(b.difference(&a), IntervalSet::from(5..10));
+}
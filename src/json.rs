@@ -0,0 +1,330 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Minimal JSON value, enough to read and write the files produced by this crate.
+///
+/// Object keys keep their insertion order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    /// Whole number written with its exact digits, `Number` rounds integers
+    /// above 2^53. Numbers read without a fraction or exponent are integers.
+    Integer(i128),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Value of the given key, if this is an object containing it.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::Integer(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(str) => Some(str),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Format the value with one array item or object entry per line.
+    pub fn pretty(&self) -> String {
+        let mut result = String::new();
+        self.write_pretty(&mut result, 0);
+        result
+    }
+
+    fn write_pretty(&self, out: &mut String, depth: usize) {
+        let indent = |depth: usize| "  ".repeat(depth);
+        match self {
+            Self::Array(values) if !values.is_empty() => {
+                out.push_str("[\n");
+                for (i, value) in values.iter().enumerate() {
+                    out.push_str(&indent(depth + 1));
+                    value.write_pretty(out, depth + 1);
+                    out.push_str(if i + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&indent(depth));
+                out.push(']');
+            }
+            Self::Object(entries) if !entries.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(&format!("{}{}: ", indent(depth + 1), quote(key)));
+                    value.write_pretty(out, depth + 1);
+                    out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&indent(depth));
+                out.push('}');
+            }
+            value => out.push_str(&value.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+fn quote(str: &str) -> String {
+    let mut result = String::with_capacity(str.len() + 2);
+    result.push('"');
+    for char in str.chars() {
+        match char {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Self::Number(_) => write!(f, "null"),
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::String(str) => write!(f, "{}", quote(str)),
+            Self::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Self::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", quote(key), value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl FromStr for Json {
+    type Err = String;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: str.chars().collect(),
+            index: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("trailing characters")),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek();
+        self.index += 1;
+        char
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("Invalid JSON at character {}: {}.", self.index, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\n' | '\r' | '\t')) {
+            self.index += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(char) if char == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> Result<Json, String> {
+        for expected in keyword.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error(&format!("expected '{}'", keyword)));
+            }
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.index;
+        while matches!(self.peek(), Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            self.index += 1;
+        }
+        let text = self.chars[start..self.index].iter().collect::<String>();
+        if let Ok(n) = text.parse() {
+            return Ok(Json::Integer(n));
+        }
+        text.parse()
+            .map(Json::Number)
+            .map_err(|_| self.error(&format!("invalid number '{}'", text)))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(result),
+                Some('\\') => match self.next() {
+                    Some('n') => result.push('\n'),
+                    Some('r') => result.push('\r'),
+                    Some('t') => result.push('\t'),
+                    Some('u') => {
+                        let code = self
+                            .chars
+                            .get(self.index..self.index + 4)
+                            .map(|chars| chars.iter().collect::<String>())
+                            .and_then(|hex| u32::from_str_radix(&hex, 16).ok())
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| self.error("invalid unicode escape"))?;
+                        self.index += 4;
+                        result.push(code);
+                    }
+                    Some(char) => result.push(char),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(char) => result.push(char),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.index += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.index += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(entries)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let value = Json::Object(vec![
+        ("day".to_string(), Json::Integer(16)),
+        ("answer".to_string(), Json::Integer(u64::MAX as i128)),
+        ("name".to_string(), Json::from("a \"quoted\"\nline")),
+        (
+            "values".to_string(),
+            Json::Array(vec![Json::Null, Json::Bool(true), Json::Number(-1.5)]),
+        ),
+    ]);
+    assert!(value.to_string().contains("18446744073709551615"));
+    assert_eq!(value.to_string().parse::<Json>(), Ok(value.clone()));
+    assert_eq!(value.pretty().parse::<Json>(), Ok(value));
+    assert_eq!("2.0".parse::<Json>(), Ok(Json::Number(2.0)));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!("{\"a\": }".parse::<Json>().is_err());
+    assert!("[1, 2".parse::<Json>().is_err());
+    assert!("1 2".parse::<Json>().is_err());
+}
@@ -0,0 +1,56 @@
+//! Advent of Code 2022 solutions and the tooling used to run, time and verify them.
+//!
+//! Every day is a `dayN` module exposing its parsed input types and the
+//! `part_one` and `part_two` solvers, and implements `solution::Solution` so
+//! it can be run through the `days` registry.
+
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod cli;
+pub mod cycle;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod days;
+pub mod error;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod inputs;
+pub mod interval;
+pub mod json;
+pub mod pattern;
+pub mod pool;
+pub mod reader;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod summary;
+pub mod timing;
+pub mod verify;
+pub mod watch;
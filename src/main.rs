@@ -1,54 +1,187 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod reader;
-
-fn main() {
-    day1::run();
-    day2::run();
-    day3::run();
-    day4::run();
-    day5::run();
-    day6::run();
-    day7::run();
-    day8::run();
-    day9::run();
-    day10::run();
-    day11::run();
-    day12::run();
-    day13::run();
-    day14::run();
-    day15::run();
-    day16::run();
-    day17::run();
-    day18::run();
-    day19::run();
-    day20::run();
-    day21::run();
-    day22::run();
-    day23::run();
-    day24::run();
-    day25::run();
+use advent_of_code_2022::inputs::{self, Example, Profile, Source};
+use advent_of_code_2022::solution::Puzzle;
+use advent_of_code_2022::summary::{self, Summary};
+use advent_of_code_2022::{
+    bench, cli, days, generate, pool, report, runner, scaffold, timing, verify, watch,
+};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    pool::install_hook();
+    match cli::parse(std::env::args().skip(1)) {
+        Ok(cli::Command::Run(options)) => {
+            let puzzles = select(&options.selection);
+            let part = options.selection.part;
+            if let Err(error) = cli::check_params(&puzzles, &options.params) {
+                eprintln!("{}\n\n{}", error, cli::params_usage(&puzzles));
+                return ExitCode::from(summary::EXIT_USAGE);
+            }
+            let sources = match (options.input.as_deref(), options.example) {
+                (Some(path), _) => match runner::read_input(path) {
+                    Ok(input) => vec![(None, Source::Reader(input))],
+                    Err(error) => return fail(error),
+                },
+                (None, Some(number)) => match Example::load(number) {
+                    Ok(example) => vec![(None, Source::Example(example))],
+                    Err(error) => return fail(error),
+                },
+                (None, None) => match profiles(&options.selection) {
+                    Ok(profiles) => profiles,
+                    Err(error) => return fail(error),
+                },
+            };
+
+            let params = &options.params;
+            let tasks = runner::tasks(&puzzles, part, &sources, options.timeout, params);
+            let mut summary = Summary::default();
+            match (options.format, options.jobs) {
+                (Some(format), jobs) => {
+                    report::run(&tasks, jobs.unwrap_or(1), format, &mut summary)
+                }
+                _ if options.time => {
+                    for (label, source) in &sources {
+                        if let Some(label) = label {
+                            println!("Profile: {}", label);
+                        }
+                        let profile = label.as_deref();
+                        let no_io = options.no_io;
+                        timing::run(&puzzles, part, no_io, source, profile, params, &mut summary);
+                    }
+                }
+                (None, Some(jobs)) => runner::run_parallel(&tasks, jobs, &mut summary),
+                (None, None) => runner::run(&tasks, &mut summary),
+            }
+
+            // Records already include their errors, keep their output parseable.
+            let several = summary.len() > 1 && !options.time;
+            if options.format.is_none() && (summary.has_failures() || several) {
+                summary.print();
+            }
+            ExitCode::from(summary.exit_code())
+        }
+        Ok(cli::Command::Bench(options)) => {
+            let puzzles = select(&options.selection);
+            if let Err(error) = cli::check_params(&puzzles, &options.params) {
+                eprintln!("{}\n\n{}", error, cli::params_usage(&puzzles));
+                return ExitCode::from(summary::EXIT_USAGE);
+            }
+            let source = match profiles(&options.selection) {
+                Ok(mut profiles) if profiles.len() == 1 => profiles.remove(0).1,
+                Ok(_) => return fail("Benchmarks require a single profile."),
+                Err(error) => return fail(error),
+            };
+            let mut summary = Summary::default();
+            match bench::run(&puzzles, &source, &options, &mut summary) {
+                Ok(()) if summary.has_failures() => {
+                    summary.print();
+                    ExitCode::from(summary.exit_code())
+                }
+                Ok(()) => ExitCode::SUCCESS,
+                Err(error) => fail(error),
+            }
+        }
+        Ok(cli::Command::Verify(options)) => {
+            let selection = &options.selection;
+            let profiles =
+                match inputs::resolve(selection.input_dir.as_deref(), selection.profile.as_deref())
+                {
+                    Ok(profiles) => profiles,
+                    Err(error) => return fail(error),
+                };
+
+            let puzzles = select(selection);
+            let mut success = true;
+            for profile in &profiles {
+                if profiles.len() > 1 {
+                    println!("Profile: {}", profile.label());
+                }
+                match verify::run(&puzzles, profile, &options) {
+                    Ok(passed) => success &= passed,
+                    Err(error) => return fail(error),
+                }
+            }
+            exit_code(success)
+        }
+        Ok(cli::Command::Watch(options)) => {
+            let selection = &options.selection;
+            let profile =
+                match inputs::resolve(selection.input_dir.as_deref(), selection.profile.as_deref())
+                {
+                    Ok(mut profiles) if profiles.len() == 1 => profiles.remove(0),
+                    Ok(_) => return fail("Watching requires a single profile."),
+                    Err(error) => return fail(error),
+                };
+            match watch::run(selection.days[0], &profile, &options) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(error) => fail(error),
+            }
+        }
+        Ok(cli::Command::NewDay(day)) => match scaffold::new_day(day) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("Wrote '{}'.", path);
+                }
+                ExitCode::SUCCESS
+            }
+            Err(error) => fail(error),
+        },
+        Ok(cli::Command::Generate(options)) => {
+            match generate::input(options.day, options.seed, options.size) {
+                // Inputs have no trailing newline, keep it that way when redirected to a file.
+                Ok(input) => {
+                    print!("{}", input);
+                    ExitCode::SUCCESS
+                }
+                Err(error) => {
+                    eprintln!("{}", error);
+                    ExitCode::from(summary::EXIT_USAGE)
+                }
+            }
+        }
+        Ok(cli::Command::Help) => {
+            println!("{}\n\n{}", cli::USAGE, cli::params_usage(days::DAYS));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, cli::USAGE);
+            ExitCode::from(summary::EXIT_USAGE)
+        }
+    }
+}
+
+fn exit_code(success: bool) -> ExitCode {
+    match success {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+fn fail(error: impl std::fmt::Display) -> ExitCode {
+    eprintln!("{}", error);
+    ExitCode::FAILURE
+}
+
+/// Input sources of the selected profiles, labelled when there are several.
+fn profiles(selection: &cli::Selection) -> Result<Vec<(Option<String>, Source)>, String> {
+    let profiles = inputs::resolve(selection.input_dir.as_deref(), selection.profile.as_deref())?;
+    let labelled = profiles.len() > 1;
+    Ok(profiles
+        .into_iter()
+        .map(|profile: Profile| {
+            let label = labelled.then(|| profile.label().to_string());
+            (label, Source::Profile(profile))
+        })
+        .collect())
+}
+
+/// Registered days of the selection, warning about the missing ones.
+fn select(selection: &cli::Selection) -> Vec<&'static dyn Puzzle> {
+    let mut puzzles = Vec::with_capacity(selection.days.len());
+    for number in &selection.days {
+        match days::get(*number) {
+            Some(day) => puzzles.push(day),
+            None => eprintln!("Day {} is not registered.", number),
+        }
+    }
+    puzzles
 }
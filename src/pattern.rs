@@ -0,0 +1,138 @@
+//! Parsing of lines with a fixed sentence around their values, such as the
+//! sensors of day 15 or the blueprints of day 19, into tuples of typed fields.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Extract the fields of a line matching a pattern such as
+/// `"Sensor at x={}, y={}: closest beacon is at x={}, y={}"` and parse them
+/// into a tuple of the field types.
+///
+/// Every `{}` captures the text up to the literal that follows it, `{_}`
+/// matches text the same way without capturing it. Runs of whitespace,
+/// including newlines, match any other run of whitespace and the captured
+/// fields are trimmed.
+/// ## Example
+/// ```rs
+/// let (name, rate): (String, usize) = pattern::parse("Valve {} has flow rate={};{_}", line)?;
+/// ```
+pub fn parse<T: FromFields>(pattern: &str, text: &str) -> Result<T, String> {
+    T::from_fields(fields(pattern, text)?)
+}
+
+/// The captured fields of the text, see `parse`.
+pub fn fields(pattern: &str, text: &str) -> Result<Vec<String>, String> {
+    let text = normalize(text);
+    let pattern = normalize(pattern);
+    let mut rest = text.as_str();
+    let mut fields = Vec::new();
+    let mut pieces = pattern.split('{');
+
+    let prefix = pieces.next().unwrap_or_default();
+    rest = rest
+        .strip_prefix(prefix)
+        .ok_or_else(|| format!("expected {:?} at {:?}", prefix, rest))?;
+
+    for piece in pieces {
+        let (placeholder, literal) = piece
+            .split_once('}')
+            .unwrap_or_else(|| panic!("unclosed placeholder in pattern {:?}", pattern));
+        let (field, next) = match literal.is_empty() {
+            true => (rest, ""),
+            false => match rest.find(literal) {
+                Some(index) => (&rest[..index], &rest[index + literal.len()..]),
+                None => return Err(format!("expected {:?} in {:?}", literal, rest)),
+            },
+        };
+        match placeholder {
+            "" => fields.push(field.trim().to_string()),
+            "_" => (),
+            _ => panic!("unknown placeholder {{{}}} in pattern", placeholder),
+        }
+        rest = next;
+    }
+
+    match rest.is_empty() {
+        true => Ok(fields),
+        false => Err(format!("unexpected {:?} at the end", rest)),
+    }
+}
+
+/// Collapse every run of whitespace into a single space.
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Types that can be built from the captured fields of a pattern, tuples of
+/// `FromStr` types.
+pub trait FromFields: Sized {
+    fn from_fields(fields: Vec<String>) -> Result<Self, String>;
+}
+
+fn field<T>(fields: &[String], index: usize) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let text = &fields[index];
+    text.parse()
+        .map_err(|error| format!("field {} {:?}: {}", index + 1, text, error))
+}
+
+macro_rules! impl_from_fields {
+    ($count:literal: $($name:ident $index:literal),+) => {
+        impl<$($name),+> FromFields for ($($name,)+)
+        where
+            $($name: FromStr, $name::Err: Display,)+
+        {
+            fn from_fields(fields: Vec<String>) -> Result<Self, String> {
+                if fields.len() != $count {
+                    return Err(format!("expected {} fields, got {}", $count, fields.len()));
+                }
+                Ok(($(field::<$name>(&fields, $index)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(1: A 0);
+impl_from_fields!(2: A 0, B 1);
+impl_from_fields!(3: A 0, B 1, C 2);
+impl_from_fields!(4: A 0, B 1, C 2, D 3);
+impl_from_fields!(5: A 0, B 1, C 2, D 3, E 4);
+impl_from_fields!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_fields!(7: A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_fields!(8: A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[test]
+fn test_parse() {
+    let pattern = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+    let line = "Sensor at x=2,  y=18:\tclosest beacon is at x=-2, y=15";
+    assert_eq!(parse(pattern, line), Ok((2, 18, -2, 15)));
+
+    let valve = "Valve {} has flow rate={}; {_} {_} to {_} {}";
+    let line = "Valve BB has flow rate=13; tunnel leads to valve CC";
+    let (name, rate, valves): (String, usize, String) = parse(valve, line).unwrap();
+    assert_eq!((name.as_str(), rate, valves.as_str()), ("BB", 13, "CC"));
+}
+
+#[test]
+fn test_errors() {
+    let pattern = "x={}, y={}";
+    assert_eq!(
+        parse::<(i32, i32)>(pattern, "x=1; y=2"),
+        Err("expected \", y=\" in \"1; y=2\"".to_string())
+    );
+    assert_eq!(
+        parse::<(i32, i32)>(pattern, "x=1, y=a"),
+        Err("field 2 \"a\": invalid digit found in string".to_string())
+    );
+    assert_eq!(
+        parse::<(i32,)>(pattern, "x=1, y=2"),
+        Err("expected 1 fields, got 2".to_string())
+    );
+    assert_eq!(
+        fields("x={}.", "x=1. y=2"),
+        Err("unexpected \" y=2\" at the end".to_string())
+    );
+}
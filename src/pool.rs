@@ -0,0 +1,102 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+
+thread_local! {
+    /// Number of nested `catch` calls running on this thread.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Location of the last panic inside `catch` on this thread.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Replace the panic hook so panics inside `catch` are not printed, their
+/// location becomes part of the error instead. Other panics are printed by
+/// the previous hook as usual.
+pub fn install_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.with(Cell::get) > 0 {
+            let location = info.location().map(|location| location.to_string());
+            LOCATION.with(|cell| *cell.borrow_mut() = location);
+        } else {
+            previous(info);
+        }
+    }));
+}
+
+/// Run `f`, turning a panic into `Err` with the panic location, when known,
+/// and message.
+pub fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    CATCHING.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|depth| depth.set(depth.get() - 1));
+    result.map_err(|payload| {
+        let message = panic_message(payload);
+        match LOCATION.with(|cell| cell.borrow_mut().take()) {
+            Some(location) => format!("{}: {}", location, message),
+            None => message,
+        }
+    })
+}
+
+/// Apply `f` to every task on up to `jobs` threads and return the results in
+/// task order.
+///
+/// A task that panics is reported as `Err` with the message of `catch`, the
+/// other tasks keep running.
+pub fn map<T, R>(jobs: usize, tasks: Vec<T>, f: impl Fn(T) -> R + Sync) -> Vec<Result<R, String>>
+where
+    T: Send,
+    R: Send,
+{
+    let count = tasks.len();
+    let queue = Mutex::new(tasks.into_iter().enumerate());
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                let Some((index, task)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let result = catch(|| f(task));
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every task was run"))
+        .collect()
+}
+
+/// Text of a panic payload, as passed to `panic!`.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_map() {
+    let results = map(3, (1..=10).collect(), |n: u32| {
+        if n == 4 {
+            panic!("four");
+        }
+        n * n
+    });
+    assert_eq!(results.len(), 10);
+    assert_eq!(results[0], Ok(1));
+    assert_eq!(results[3], Err("four".to_string()));
+    assert_eq!(results[9], Ok(100));
+    assert_eq!(map(4, Vec::<u32>::new(), |n| n), vec![]);
+}
@@ -1,10 +1,27 @@
 #![allow(dead_code)]
 
+use crate::error::Error;
+use crate::grid::{Grid, Position};
+use std::fmt::Display;
+use std::io::Read;
+use std::str::FromStr;
+
 /// Reader used to parse text file content.
 ///
-/// Use `reader::open(path: &str)` to create a new `Reader` from a text file.
+/// Use `reader::open(path: &str)` to create a new `Reader` from a text file,
+/// `reader::try_read` to read any `impl Read` such as stdin, or `Reader::from`
+/// for text that is already in memory.
+///
+/// The parsing methods panic on the first part that fails to parse, their
+/// `try_` counterparts return an `Error` with the path, line number and text
+/// of the offending part instead. The `_all` variants keep parsing after a
+/// failure and return every error at once.
+#[derive(Clone)]
 pub struct Reader {
     text: String,
+    path: Option<String>,
+    /// 1-based line number of the first line of the text in its file.
+    line: usize,
 }
 
 impl Reader {
@@ -38,9 +55,23 @@ impl Reader {
             .collect()
     }
 
+    /// Current content divided into blocks by empty lines, as readers that
+    /// keep the path and line numbers of the original content. Useful for
+    /// inputs made of sections that are parsed differently.
+    pub fn blocks(self) -> Vec<Reader> {
+        let (path, start) = (self.path.clone(), self.line);
+        split_with_line_numbers(&self.text(), "\n\n", start)
+            .map(|(line, part)| Reader {
+                text: part.to_string(),
+                path: path.clone(),
+                line,
+            })
+            .collect()
+    }
+
     /// Current content divided by empty lines and the resulting blocks
     /// parsed into the given type.
-    /// 
+    ///
     /// Single block can still contain multiple lines.
     /// Line changes `\r\n` will be replaced by `\n`.
     /// ## Example
@@ -56,9 +87,9 @@ impl Reader {
     /// ```
     /// ## Note
     /// You can provide support for custom structs by implementing the `FromStr` trait.
-    /// ```
+    /// ```rs
     /// use std::{str::FromStr, string::ParseError};
-    /// 
+    ///
     /// struct Example {
     ///     a: usize,
     ///     b: usize,
@@ -86,21 +117,39 @@ impl Reader {
     /// ```
     pub fn split_on_empty_line_into<T>(self) -> Vec<T>
     where
-        T: core::str::FromStr,
+        T: FromStr,
+        T::Err: Display,
     {
-        self.text()
-            .split("\n\n")
-            .map(|part| match part.parse::<T>() {
-                Ok(value) => value,
-                _ => panic!(
-                    "Unable to parse \"{:?}\" into the given type \"{:?}\".",
-                    part,
-                    std::any::type_name::<T>()
-                ),
-            })
+        self.try_split_on_empty_line_into().unwrap_or_else(fail)
+    }
+
+    /// Fallible version of `split_on_empty_line_into`, the reported line
+    /// number is the first line of the offending block.
+    pub fn try_split_on_empty_line_into<T>(self) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        split_with_line_numbers(&self.text(), "\n\n", start)
+            .map(|(line, part)| parse(&path, line, part))
             .collect()
     }
 
+    /// Version of `try_split_on_empty_line_into` that parses every block and
+    /// returns all the failures instead of only the first one.
+    pub fn try_split_on_empty_line_into_all<T>(self) -> Result<Vec<T>, Vec<Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        collect_all(
+            split_with_line_numbers(&self.text(), "\n\n", start)
+                .map(|(line, part)| parse(&path, line, part)),
+        )
+    }
+
     /// Process the file content as a single string, splitting it on a given pattern, and
     /// parsing the resulting parts into the given type.
     /// ## Example
@@ -112,7 +161,7 @@ impl Reader {
     /// ```
     /// ## Note
     /// You can provide support for custom structs by implementing the `FromStr` trait.
-    /// ```
+    /// ```rs
     /// use std::{str::FromStr, string::ParseError};
     ///
     /// struct Example {
@@ -137,21 +186,38 @@ impl Reader {
     /// ```
     pub fn split_line_into<T>(self, pattern: &str) -> Vec<T>
     where
-        T: core::str::FromStr,
+        T: FromStr,
+        T::Err: Display,
     {
-        self.text()
-            .split(pattern)
-            .map(|part| match part.parse::<T>() {
-                Ok(value) => value,
-                _ => panic!(
-                    "Unable to parse \"{:?}\" into the given type \"{:?}\".",
-                    part,
-                    std::any::type_name::<T>()
-                ),
-            })
+        self.try_split_line_into(pattern).unwrap_or_else(fail)
+    }
+
+    /// Fallible version of `split_line_into`.
+    pub fn try_split_line_into<T>(self, pattern: &str) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        split_with_line_numbers(&self.text(), pattern, start)
+            .map(|(line, part)| parse(&path, line, part))
             .collect()
     }
 
+    /// Version of `try_split_line_into` that parses every part and returns all
+    /// the failures instead of only the first one.
+    pub fn try_split_line_into_all<T>(self, pattern: &str) -> Result<Vec<T>, Vec<Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        collect_all(
+            split_with_line_numbers(&self.text(), pattern, start)
+                .map(|(line, part)| parse(&path, line, part)),
+        )
+    }
+
     /// Return the file content lines parsed into the given type.
     /// ## Example
     /// ```rs
@@ -165,7 +231,7 @@ impl Reader {
     /// ```
     /// ## Note
     /// You can provide support for custom structs by implementing the `FromStr` trait.
-    /// ```
+    /// ```rs
     /// use std::{str::FromStr, string::ParseError};
     ///
     /// struct Example {
@@ -191,24 +257,150 @@ impl Reader {
     /// ```
     pub fn lines_as<T>(self) -> Vec<T>
     where
-        T: core::str::FromStr,
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.try_lines_as().unwrap_or_else(fail)
+    }
+
+    /// Fallible version of `lines_as`.
+    /// ## Example
+    /// ```rs
+    /// // file.txt
+    /// // 1
+    /// // a
+    /// try_open("file.txt")?.try_lines_as::<usize>()
+    /// // Err(file.txt:2: unable to parse "a" into "usize": invalid digit found in string)
+    /// ```
+    pub fn try_lines_as<T>(self) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
     {
+        let (path, start) = (self.path.clone(), self.line);
         self.text()
             .lines()
-            .map(|line| match line.parse::<T>() {
-                Ok(value) => value,
-                _ => panic!(
-                    "Unable to parse \"{:?}\" into the given type \"{:?}\".",
-                    line,
-                    std::any::type_name::<T>()
-                ),
-            })
+            .enumerate()
+            .map(|(index, line)| parse(&path, start + index, line))
             .collect()
     }
 
-    /// Return the file content lines passed through the given function.
+    /// Version of `try_lines_as` that parses every line and returns all the
+    /// failures instead of only the first one.
+    /// ## Example
+    /// ```rs
+    /// // file.txt
+    /// // 1
+    /// // a
+    /// // 3
+    /// // b
+    /// try_open("file.txt")?.try_lines_as_all::<usize>()
+    /// // Err([file.txt:2: unable to parse "a" ..., file.txt:4: unable to parse "b" ...])
+    /// ```
+    pub fn try_lines_as_all<T>(self) -> Result<Vec<T>, Vec<Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        collect_all(
+            self.text()
+                .lines()
+                .enumerate()
+                .map(|(index, line)| parse(&path, start + index, line)),
+        )
+    }
+
+    /// Version of `try_lines_as_all` that parses the lines with a function,
+    /// e.g. to check them against the rest of the input.
     /// ## Example
+    /// ```rs
+    /// // file.txt
+    /// // 1
+    /// // 7
+    /// try_open("file.txt")?.try_lines_with_all(|line| match line.parse::<usize>() {
+    ///     Ok(n) if n < 5 => Ok(n),
+    ///     _ => Err("expected a number below 5"),
+    /// })
+    /// // Err([file.txt:2: unable to parse "7" into "usize": expected a number below 5])
     /// ```
+    pub fn try_lines_with_all<T, E>(
+        self,
+        mut f: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<Vec<T>, Vec<Error>>
+    where
+        E: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        collect_all(self.text().lines().enumerate().map(|(index, line)| {
+            f(line).map_err(|error| Error::Parse {
+                path: path.clone(),
+                line: start + index,
+                text: line.to_string(),
+                type_name: std::any::type_name::<T>(),
+                reason: error.to_string(),
+            })
+        }))
+    }
+
+    /// Error pointing past the last line, for input that lacks something no
+    /// single line can be blamed for.
+    pub fn error_at_end<T>(&self, reason: impl Display) -> Error {
+        Error::Parse {
+            path: self.path.clone(),
+            line: self.line + self.text.lines().count(),
+            text: String::new(),
+            type_name: std::any::type_name::<T>(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Grid with a cell per character, mapped by `cell` from its position and
+    /// character. The error points at the first line with a character `cell`
+    /// rejects or with a different length than the first line.
+    /// ## Example
+    /// ```rs
+    /// // file.txt
+    /// // 12
+    /// // 3x
+    /// try_open("file.txt")?.try_grid(|_, c| c.to_digit(10).ok_or("not a digit"))
+    /// // Err(file.txt:2: unable to parse "3x" into "u32": not a digit)
+    /// ```
+    pub fn try_grid<T, E>(
+        self,
+        mut cell: impl FnMut(Position, char) -> Result<T, E>,
+    ) -> Result<Grid<T>, Error>
+    where
+        E: Display,
+    {
+        let (path, start) = (self.path.clone(), self.line);
+        let text = self.text();
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (y, line) in text.lines().enumerate() {
+            let fail = |reason: String| Error::Parse {
+                path: path.clone(),
+                line: start + y,
+                text: line.to_string(),
+                type_name: std::any::type_name::<T>(),
+                reason,
+            };
+            let length = line.chars().count();
+            if length != width {
+                return Err(fail(format!("expected {} cells, got {}", width, length)));
+            }
+            for (x, c) in line.chars().enumerate() {
+                cells.push(cell((x, y), c).map_err(|error| fail(error.to_string()))?);
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width, height, cells))
+    }
+
+    /// Return the file content lines passed through the given function.
+    /// ## Example
+    /// ```rs
     /// fn my_parse_function(str: &str) -> usize {
     ///     str.parse().unwrap_or(0)
     /// }
@@ -222,7 +414,7 @@ impl Reader {
     /// ```
     /// ## Note
     /// This provides an alternative way to create custom structs using a non-trait function.
-    /// ```
+    /// ```rs
     /// struct Example {
     ///     a: usize,
     ///     b: usize,
@@ -250,7 +442,208 @@ impl Reader {
 
 /// Open text file in the given path and return its contents as a `Reader`.
 pub fn open(path: &str) -> Reader {
-    Reader {
-        text: std::fs::read_to_string(path).expect("file not found"),
+    try_open(path).unwrap_or_else(fail)
+}
+
+/// Fallible version of `open`.
+pub fn try_open(path: &str) -> Result<Reader, Error> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Reader {
+            text,
+            path: Some(path.to_string()),
+            line: 1,
+        }),
+        Err(error) => Err(Error::Io {
+            path: path.to_string(),
+            error,
+        }),
     }
 }
+
+/// Read the whole content of the given source, e.g. `std::io::stdin()`.
+///
+/// The name is used as the path in error messages.
+pub fn try_read(mut source: impl Read, name: &str) -> Result<Reader, Error> {
+    let mut text = String::new();
+    match source.read_to_string(&mut text) {
+        Ok(_) => Ok(Reader {
+            text,
+            path: Some(name.to_string()),
+            line: 1,
+        }),
+        Err(error) => Err(Error::Io {
+            path: name.to_string(),
+            error,
+        }),
+    }
+}
+
+impl From<&str> for Reader {
+    fn from(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            path: None,
+            line: 1,
+        }
+    }
+}
+
+fn fail<T>(error: Error) -> T {
+    panic!("{}", error)
+}
+
+fn parse<T>(path: &Option<String>, line: usize, text: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse().map_err(|error: T::Err| Error::Parse {
+        path: path.clone(),
+        line,
+        text: text.to_string(),
+        type_name: std::any::type_name::<T>(),
+        reason: error.to_string(),
+    })
+}
+
+/// Collect every value, or every error if any of the parts failed to parse.
+fn collect_all<T>(results: impl Iterator<Item = Result<T, Error>>) -> Result<Vec<T>, Vec<Error>> {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error),
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(values),
+        false => Err(errors),
+    }
+}
+
+/// Split the text on the given pattern, pairing each part with the line number
+/// it starts on, counting from the line number `start` of the text.
+fn split_with_line_numbers<'a>(
+    text: &'a str,
+    pattern: &'a str,
+    start: usize,
+) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    let pattern_lines = pattern.matches('\n').count();
+    let mut line = start;
+    text.split(pattern).map(move |part| {
+        let start = line;
+        line += part.matches('\n').count() + pattern_lines;
+        (start, part)
+    })
+}
+
+#[test]
+fn test_try_lines_as() {
+    let reader = Reader {
+        text: "1\r\n2\r\na".to_string(),
+        path: Some("file.txt".to_string()),
+        line: 1,
+    };
+    assert_eq!(
+        reader.try_lines_as::<usize>().unwrap_err().to_string(),
+        "file.txt:3: unable to parse \"a\" into \"usize\": invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_try_split_on_empty_line_into() {
+    let reader = Reader {
+        text: "1\n\n2\n3\n\nx".to_string(),
+        path: None,
+        line: 1,
+    };
+    match reader.try_split_on_empty_line_into::<usize>() {
+        Err(Error::Parse { line, text, .. }) => assert_eq!((line, text.as_str()), (3, "2\n3")),
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn test_try_lines_as_all() {
+    let reader = Reader {
+        text: "1\na\n3\nb".to_string(),
+        path: None,
+        line: 1,
+    };
+    let lines = |errors: Vec<Error>| {
+        errors
+            .iter()
+            .map(|error| match error {
+                Error::Parse { line, .. } => *line,
+                error => panic!("Unexpected error: {}", error),
+            })
+            .collect::<Vec<usize>>()
+    };
+    assert_eq!(
+        reader.clone().try_lines_as_all::<usize>().map_err(lines),
+        Err(vec![2, 4])
+    );
+    assert_eq!(
+        reader.try_lines_as_all::<String>().unwrap(),
+        vec!["1", "a", "3", "b"]
+    );
+}
+
+#[test]
+fn test_try_read() {
+    let reader = try_read("1\n2\n".as_bytes(), "<stdin>").unwrap();
+    assert_eq!(reader.lines_as::<usize>(), vec![1, 2]);
+    assert_eq!(Reader::from("3\n4").lines_as::<usize>(), vec![3, 4]);
+}
+
+#[test]
+fn test_blocks() {
+    let blocks = Reader::from("1\n2\n\n3\nx\n\n4").blocks();
+    assert_eq!(blocks.len(), 3);
+    match blocks[1].clone().try_lines_as::<usize>() {
+        Err(Error::Parse { line, text, .. }) => assert_eq!((line, text.as_str()), (5, "x")),
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    let grid = blocks[0]
+        .clone()
+        .try_grid(|_, c| c.to_digit(10).ok_or("not a digit"));
+    assert_eq!(grid.unwrap().row(1), [2]);
+
+    let grid = Reader::from("12\n3x").try_grid(|_, c| c.to_digit(10).ok_or("not a digit"));
+    assert_eq!(
+        grid.unwrap_err().to_string(),
+        "<input>:2: unable to parse \"3x\" into \"u32\": not a digit"
+    );
+    let grid = Reader::from("12\n3").try_grid(|_, c| Ok::<char, String>(c));
+    assert_eq!(
+        grid.unwrap_err().to_string(),
+        "<input>:2: unable to parse \"3\" into \"char\": expected 2 cells, got 1"
+    );
+}
+
+#[test]
+fn test_try_lines_with_all() {
+    let reader = Reader::from("1\n7\n2\n9");
+    let below = |line: &str| match line.parse::<usize>() {
+        Ok(n) if n < 5 => Ok(n),
+        _ => Err("expected a number below 5"),
+    };
+    let lines = |errors: Vec<Error>| {
+        errors
+            .iter()
+            .map(|error| match error {
+                Error::Parse { line, .. } => *line,
+                error => panic!("Unexpected error: {}", error),
+            })
+            .collect::<Vec<usize>>()
+    };
+    assert_eq!(
+        reader.clone().try_lines_with_all(below).map_err(lines),
+        Err(vec![2, 4])
+    );
+    assert_eq!(
+        reader.error_at_end::<usize>("no zero").to_string(),
+        "<input>:5: unable to parse \"\" into \"usize\": no zero"
+    );
+}
@@ -0,0 +1,177 @@
+use crate::json::Json;
+use crate::runner::{self, Task};
+use crate::solution::Answer;
+use crate::summary::Summary;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Machine-readable output format of the `run` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Unknown format '{}'.", str)),
+        }
+    }
+}
+
+/// Outcome of solving a single part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// Label of the profile the input was read from, if there are several.
+    pub profile: Option<String>,
+    pub day: u8,
+    pub part: u8,
+    /// The answer, or a description of why the day failed.
+    pub result: Result<Answer, String>,
+    /// Time spent parsing the input and solving the part, without file I/O.
+    pub elapsed: Option<Duration>,
+}
+
+impl Record {
+    pub fn to_json(&self) -> Json {
+        let (answer, kind, error) = match &self.result {
+            Ok(answer) => (
+                answer_to_json(answer),
+                Json::from(answer.kind()),
+                Json::Null,
+            ),
+            Err(error) => (Json::Null, Json::Null, Json::from(error.as_str())),
+        };
+        Json::Object(vec![
+            (
+                "profile".to_string(),
+                self.profile.as_deref().map_or(Json::Null, Json::from),
+            ),
+            ("day".to_string(), Json::Integer(self.day.into())),
+            ("part".to_string(), Json::Integer(self.part.into())),
+            ("answer".to_string(), answer),
+            ("type".to_string(), kind),
+            (
+                "elapsed_ns".to_string(),
+                self.elapsed.map_or(Json::Null, |elapsed| {
+                    Json::Integer(elapsed.as_nanos() as i128)
+                }),
+            ),
+            ("error".to_string(), error),
+        ])
+    }
+
+    pub fn to_csv(&self) -> String {
+        let (answer, kind, error) = match &self.result {
+            Ok(answer) => (answer.to_string(), answer.kind(), ""),
+            Err(error) => (String::new(), "", error.as_str()),
+        };
+        let elapsed = self
+            .elapsed
+            .map_or(String::new(), |elapsed| elapsed.as_nanos().to_string());
+        [
+            self.profile.as_deref().unwrap_or(""),
+            &self.day.to_string(),
+            &self.part.to_string(),
+            &answer,
+            kind,
+            &elapsed,
+            error,
+        ]
+        .map(csv_field)
+        .join(",")
+    }
+}
+
+/// Header row matching `Record::to_csv`.
+pub const CSV_HEADER: &str = "profile,day,part,answer,type,elapsed_ns,error";
+
+fn answer_to_json(answer: &Answer) -> Json {
+    match answer {
+        Answer::Unsigned(n) => Json::Integer((*n).into()),
+        Answer::Signed(n) => Json::Integer((*n).into()),
+        Answer::Text(text) => Json::from(text.as_str()),
+        Answer::Image(rows) => {
+            Json::Array(rows.iter().map(|row| Json::from(row.as_str())).collect())
+        }
+        Answer::None => Json::Null,
+    }
+}
+
+/// Quote the field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+/// Solve the tasks on up to `jobs` threads and print one record per part in
+/// the given format, recording the days in the summary.
+pub fn run(tasks: &[Task], jobs: usize, format: Format, summary: &mut Summary) {
+    let mut records = Vec::with_capacity(tasks.len());
+    for (task, outcome) in tasks.iter().zip(runner::solve_all(tasks, jobs)) {
+        summary.add(task.profile, task.puzzle.day(), outcome.as_ref().err());
+        let (result, elapsed) = match outcome {
+            Ok((Answer::None, _)) => continue,
+            Ok((answer, elapsed)) => (Ok(answer), Some(elapsed)),
+            Err(failure) => (Err(failure.to_string()), None),
+        };
+        records.push(Record {
+            profile: task.profile.map(String::from),
+            day: task.puzzle.day(),
+            part: task.part,
+            result,
+            elapsed,
+        });
+    }
+
+    print(&records, format);
+}
+
+pub fn print(records: &[Record], format: Format) {
+    match format {
+        Format::Json => {
+            let json = Json::Array(records.iter().map(Record::to_json).collect());
+            println!("{}", json.pretty());
+        }
+        Format::Csv => {
+            println!("{}", CSV_HEADER);
+            for record in records {
+                println!("{}", record.to_csv());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_record() {
+    let record = Record {
+        profile: None,
+        day: 10,
+        part: 2,
+        result: Ok(Answer::Image(vec!["#.".to_string(), ".#".to_string()])),
+        elapsed: Some(Duration::from_nanos(1500)),
+    };
+    assert_eq!(
+        record.to_json().to_string(),
+        r##"{"profile":null,"day":10,"part":2,"answer":["#.",".#"],"type":"image","elapsed_ns":1500,"error":null}"##
+    );
+    assert_eq!(record.to_csv(), ",10,2,\"#.\n.#\",image,1500,");
+
+    let record = Record {
+        profile: Some("alice".to_string()),
+        day: 4,
+        part: 1,
+        result: Err("files/day4.txt:2: unable to parse \"2-x,1-2\"".to_string()),
+        elapsed: None,
+    };
+    assert_eq!(
+        record.to_csv(),
+        "alice,4,1,,,,\"files/day4.txt:2: unable to parse \"\"2-x,1-2\"\"\""
+    );
+}
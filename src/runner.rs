@@ -0,0 +1,283 @@
+use crate::cancel::{self, Token};
+use crate::cli::Part;
+use crate::error::Error;
+use crate::inputs::Source;
+use crate::pool;
+use crate::reader::{self, Reader};
+use crate::solution::{Answer, Params, Puzzle};
+use crate::summary::Summary;
+use crate::verify;
+use std::fmt;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Read an input given on the command line, `-` reads stdin.
+pub fn read_input(path: &str) -> Result<Reader, Error> {
+    match path {
+        "-" => reader::try_read(std::io::stdin().lock(), "<stdin>"),
+        path => reader::try_open(path),
+    }
+}
+
+/// Parse the input of the given day and solve a single part.
+pub fn solve(puzzle: &dyn Puzzle, part: u8, source: &Source) -> Result<Answer, Error> {
+    let input = puzzle.parse(source.load(puzzle)?)?;
+    Ok(puzzle.solve_with(part, input, source.params(puzzle)))
+}
+
+/// Parse the input of the given day and solve a single part, measuring the
+/// time spent after the input was read.
+pub fn solve_timed(
+    puzzle: &dyn Puzzle,
+    part: u8,
+    source: &Source,
+) -> Result<(Answer, Duration), Error> {
+    solve_read(puzzle, part, source.load(puzzle)?, source.params(puzzle))
+}
+
+/// Parse an input that was already read and solve a single part with the
+/// parameter overrides, measuring the time spent.
+fn solve_read(
+    puzzle: &dyn Puzzle,
+    part: u8,
+    input: Reader,
+    params: &[(String, i64)],
+) -> Result<(Answer, Duration), Error> {
+    let start = Instant::now();
+    let answer = puzzle.solve_with(part, puzzle.parse(input)?, params);
+    Ok((answer, start.elapsed()))
+}
+
+/// Reason a part could not be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The input could not be read or parsed.
+    Error(String),
+    /// The solver panicked, with the panic location and message.
+    Panic(String),
+    /// The solver did not finish within the timeout.
+    Timeout(Duration),
+    /// The answer to an example differs from the expected one, with the
+    /// difference.
+    Mismatch(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error(error) => write!(f, "{}", error),
+            Self::Panic(message) => write!(f, "panicked at {}", message),
+            Self::Timeout(timeout) => write!(f, "timed out after {:?}", timeout),
+            Self::Mismatch(diff) if diff.contains('\n') => write!(f, "wrong answer:\n{}", diff),
+            Self::Mismatch(diff) => write!(f, "wrong answer, {}", diff),
+        }
+    }
+}
+
+/// A single part of a day to solve with the input of a source.
+#[derive(Clone, Copy)]
+pub struct Task<'a> {
+    /// Label of the profile of the source, if there are several.
+    pub profile: Option<&'a str>,
+    pub source: &'a Source,
+    pub puzzle: &'static dyn Puzzle,
+    pub part: u8,
+    /// Time after which the part is given up on, `None` to wait for it.
+    pub timeout: Option<Duration>,
+    /// Parameter overrides from the command line, taking precedence over the
+    /// ones of the source.
+    pub params: &'a [(String, i64)],
+}
+
+/// Answer of a task and the time spent solving it, or the reason it failed.
+pub type Outcome = Result<(Answer, Duration), Failure>;
+
+/// Every selected part of every given day for every source, in order.
+pub fn tasks<'a>(
+    puzzles: &[&'static dyn Puzzle],
+    part: Part,
+    sources: &'a [(Option<String>, Source)],
+    timeout: Option<Duration>,
+    params: &'a [(String, i64)],
+) -> Vec<Task<'a>> {
+    let mut tasks = Vec::new();
+    for (profile, source) in sources {
+        for puzzle in puzzles {
+            for n in (1..=2).filter(|n| part.includes(*n)) {
+                tasks.push(Task {
+                    profile: profile.as_deref(),
+                    source,
+                    puzzle: *puzzle,
+                    part: n,
+                    timeout,
+                    params,
+                });
+            }
+        }
+    }
+    tasks
+}
+
+/// Solve a task, catching panics of the solver and giving up on it once its
+/// timeout has elapsed. Answers to examples are compared with the expected ones.
+pub fn solve_task(task: &Task) -> Outcome {
+    let input = task
+        .source
+        .load(task.puzzle)
+        .map_err(|error| Failure::Error(error.to_string()))?;
+    let params = [task.source.params(task.puzzle), task.params].concat();
+    let outcome = match task.timeout {
+        Some(timeout) => solve_with_timeout(task.puzzle, task.part, input, params, timeout),
+        None => solve_caught(task.puzzle, task.part, input, &params),
+    }?;
+    match expected(task) {
+        Some(expected) if expected.to_string() != outcome.0.to_string() => {
+            Err(Failure::Mismatch(verify::diff(expected, &outcome.0)))
+        }
+        _ => Ok(outcome),
+    }
+}
+
+/// Expected answer of the task, if it solves an example with a known answer.
+/// The answers hold for the parameters of the example, there is none once the
+/// command line overrides a parameter of the part.
+fn expected<'a>(task: &Task<'a>) -> Option<&'a Answer> {
+    match task.source {
+        Source::Example(_) if overridden(task) => None,
+        Source::Example(example) => example.answers.get(task.puzzle.day(), task.part),
+        _ => None,
+    }
+}
+
+fn overridden(task: &Task) -> bool {
+    Params::new(task.puzzle.params(), task.part, task.params).is_overridden()
+}
+
+fn solve_caught(puzzle: &dyn Puzzle, part: u8, input: Reader, params: &[(String, i64)]) -> Outcome {
+    match pool::catch(|| solve_read(puzzle, part, input, params)) {
+        Ok(Ok(solved)) => Ok(solved),
+        Ok(Err(error)) => Err(Failure::Error(error.to_string())),
+        Err(message) => Err(Failure::Panic(message)),
+    }
+}
+
+/// Solve on a separate thread, waiting at most `timeout` for the answer.
+///
+/// The token of the solver is cancelled on timeout. A solver that does not
+/// call `cancel::check` cannot be stopped and keeps running in the background
+/// until it finishes or the program exits.
+fn solve_with_timeout(
+    puzzle: &'static dyn Puzzle,
+    part: u8,
+    input: Reader,
+    params: Vec<(String, i64)>,
+    timeout: Duration,
+) -> Outcome {
+    let token = Token::with_timeout(timeout);
+    let (sender, receiver) = mpsc::channel();
+    let worker = token.clone();
+    thread::spawn(move || {
+        let outcome = cancel::scope(worker, || solve_caught(puzzle, part, input, &params));
+        // Nobody is listening anymore once the task timed out.
+        let _ = sender.send(outcome);
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Err(Failure::Panic(_))) if token.is_cancelled() => Err(Failure::Timeout(timeout)),
+        Ok(outcome) => outcome,
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(Failure::Timeout(timeout))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(Failure::Panic(
+            "solver thread exited without an answer".to_string(),
+        )),
+    }
+}
+
+/// Solve the tasks on up to `jobs` threads, returning the outcomes in task order.
+pub fn solve_all(tasks: &[Task], jobs: usize) -> Vec<Outcome> {
+    pool::map(jobs, tasks.to_vec(), |task| solve_task(&task))
+        .into_iter()
+        .map(|outcome| outcome.unwrap_or_else(|message| Err(Failure::Panic(message))))
+        .collect()
+}
+
+/// Solve and print the tasks one by one, printing every answer as soon as it
+/// is known, and record the days in the summary.
+pub fn run(tasks: &[Task], summary: &mut Summary) {
+    let mut printer = Printer::default();
+    for task in tasks {
+        printer.start(task);
+        let outcome = solve_task(task);
+        printer.print(task, &outcome, summary);
+    }
+}
+
+/// Solve every task on up to `jobs` threads, then print the results in the
+/// same layout as `run`.
+pub fn run_parallel(tasks: &[Task], jobs: usize, summary: &mut Summary) {
+    let mut printer = Printer::default();
+    for (task, outcome) in tasks.iter().zip(solve_all(tasks, jobs)) {
+        printer.start(task);
+        printer.print(task, &outcome, summary);
+    }
+}
+
+/// Prints the profile and day headers as the tasks change.
+#[derive(Default)]
+struct Printer<'a> {
+    current: Option<(Option<&'a str>, u8)>,
+}
+
+impl<'a> Printer<'a> {
+    fn start(&mut self, task: &Task<'a>) {
+        let current = (task.profile, task.puzzle.day());
+        if self.current == Some(current) {
+            return;
+        }
+        if self.current.map(|(profile, _)| profile) != Some(task.profile) {
+            if let Some(profile) = task.profile {
+                println!("Profile: {}", profile);
+            }
+        }
+        match task.source {
+            Source::Example(example) => println!(
+                "Day {}: {} (example {})",
+                task.puzzle.day(),
+                task.puzzle.title(),
+                example.number
+            ),
+            _ => println!("Day {}: {}", task.puzzle.day(), task.puzzle.title()),
+        }
+        self.current = Some(current);
+    }
+
+    fn print(&self, task: &Task, outcome: &Outcome, summary: &mut Summary) {
+        let note = match (task.source, expected(task)) {
+            (Source::Example(_), Some(_)) => " (as expected)",
+            (Source::Example(_), None) if overridden(task) => " (not checked, --param given)",
+            (Source::Example(_), None) => " (no expected answer)",
+            _ => "",
+        };
+        match outcome {
+            Ok((Answer::None, _)) => (),
+            Ok((answer @ Answer::Image(_), _)) => {
+                println!("\tPart {}:{}\n{}", task.part, note, answer)
+            }
+            Ok((answer, _)) => println!("\tPart {}: {}{}", task.part, answer, note),
+            Err(failure @ Failure::Mismatch(_)) => {
+                for line in failure.to_string().lines() {
+                    eprintln!("\tPart {}: {}", task.part, line);
+                }
+            }
+            Err(failure) => {
+                for line in failure.to_string().lines() {
+                    eprintln!("\tPart {}: error: {}", task.part, line);
+                }
+            }
+        }
+        summary.add(task.profile, task.puzzle.day(), outcome.as_ref().err());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::inputs;
+use std::path::Path;
+
+const README: &str = "readme.md";
+const LIB: &str = "src/lib.rs";
+const DAYS: &str = "src/days.rs";
+
+/// Create the module of a new day from the readme template, its empty input
+/// files, and register it in `src/lib.rs` and `src/days.rs`.
+///
+/// Nothing is written if any of the files already exist or the day is already
+/// declared. Returns the paths of the created and updated files.
+pub fn new_day(day: u8) -> Result<Vec<String>, String> {
+    let module = format!("src/day{}.rs", day);
+    let input = format!("{}/day{}.txt", inputs::DEFAULT_DIR, day);
+    let test_input = format!("{}/day{}_test.txt", inputs::DEFAULT_DIR, day);
+    for path in [&module, &input, &test_input] {
+        if Path::new(path).exists() {
+            return Err(format!("Refusing to overwrite '{}'.", path));
+        }
+    }
+
+    let source = render(template(&read(README)?)?, day);
+    let lib = declare_module(&read(LIB)?, day)?;
+    let days = register_day(&read(DAYS)?, day)?;
+
+    write(&module, &source)?;
+    write(&input, "")?;
+    write(&test_input, "")?;
+    write(LIB, &lib)?;
+    write(DAYS, &days)?;
+    Ok(vec![
+        module,
+        input,
+        test_input,
+        LIB.to_string(),
+        DAYS.to_string(),
+    ])
+}
+
+fn read(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|error| format!("Unable to read '{}': {}", path, error))
+}
+
+fn write(path: &str, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|error| format!("Unable to write '{}': {}", path, error))
+}
+
+/// The first `rs` code block following the `## Template` heading.
+fn template(readme: &str) -> Result<&str, String> {
+    let missing = || format!("No template found in '{}'.", README);
+    let (_, section) = readme.split_once("## Template").ok_or_else(missing)?;
+    let (_, block) = section.split_once("```rs\n").ok_or_else(missing)?;
+    let (code, _) = block.split_once("```").ok_or_else(missing)?;
+    Ok(code)
+}
+
+/// Fill in the day number placeholders of the template.
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("DayX", &format!("Day{}", day))
+        .replace("const DAY: u8 = 0;", &format!("const DAY: u8 = {};", day))
+        .replace("files/day_test.txt", &format!("files/day{}_test.txt", day))
+}
+
+/// Add `pub mod dayN;` to the sorted module declarations of `lib.rs`.
+fn declare_module(lib: &str, day: u8) -> Result<String, String> {
+    let declaration = format!("pub mod day{};", day);
+    insert_sorted(lib, &declaration, |line| line.starts_with("pub mod "))
+        .ok_or_else(|| format!("Module day{} is already declared in '{}'.", day, LIB))
+}
+
+/// Add `&crate::dayN::DayN` to the registry in `days.rs`, keeping days in order.
+fn register_day(days: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    &crate::day{}::Day{},", day, day);
+    let number = |line: &str| {
+        line.trim()
+            .strip_prefix("&crate::day")
+            .and_then(|rest| rest.split_once("::"))
+            .and_then(|(n, _)| n.parse::<u8>().ok())
+    };
+
+    let mut lines = days.lines().collect::<Vec<&str>>();
+    if lines.iter().any(|line| number(line) == Some(day)) {
+        return Err(format!("Day {} is already registered in '{}'.", day, DAYS));
+    }
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or_else(|| format!("No DAYS registry found in '{}'.", DAYS))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "];")
+            .ok_or_else(|| format!("Unterminated DAYS registry in '{}'.", DAYS))?;
+    let index = (start + 1..end)
+        .find(|i| number(lines[*i]).map_or(false, |n| n > day))
+        .unwrap_or(end);
+    lines.insert(index, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Insert the line among the lines matching `group`, in the position
+/// `rustfmt` sorts it to. Returns `None` if the line is already present.
+fn insert_sorted(text: &str, line: &str, group: impl Fn(&str) -> bool) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<&str>>();
+    if lines.contains(&line) {
+        return None;
+    }
+    let key = |line: &str| line.trim_end_matches(';').to_string();
+    let index = lines
+        .iter()
+        .position(|existing| group(existing) && key(existing) > key(line))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|existing| group(existing))
+                .map(|i| i + 1)
+        })
+        .unwrap_or(0);
+    lines.insert(index, line);
+    Some(lines.join("\n") + "\n")
+}
+
+#[test]
+fn test_render() {
+    let readme = "# Title\n## Template\nText\n```rs\npub struct DayX;\nconst DAY: u8 = 0;\nreader::open(\"files/day_test.txt\")\n```\n";
+    assert_eq!(
+        render(template(readme).unwrap(), 7),
+        "pub struct Day7;\nconst DAY: u8 = 7;\nreader::open(\"files/day7_test.txt\")\n"
+    );
+}
+
+#[test]
+fn test_register() {
+    let lib = "//! Docs\n\npub mod cli;\npub mod day1;\npub mod day20;\npub mod days;\n";
+    assert_eq!(
+        declare_module(lib, 2).unwrap(),
+        "//! Docs\n\npub mod cli;\npub mod day1;\npub mod day2;\npub mod day20;\npub mod days;\n"
+    );
+    assert!(declare_module(lib, 20).is_err());
+
+    let days = "pub const DAYS: &[&dyn Puzzle] = &[\n    &crate::day1::Day1,\n    &crate::day10::Day10,\n];\n";
+    assert_eq!(
+        register_day(days, 2).unwrap(),
+        "pub const DAYS: &[&dyn Puzzle] = &[\n    &crate::day1::Day1,\n    &crate::day2::Day2,\n    &crate::day10::Day10,\n];\n"
+    );
+    assert!(register_day(days, 10).is_err());
+}
@@ -0,0 +1,251 @@
+//! Graph searches over implicit graphs: `bfs`, `explore`, `dijkstra` and
+//! `astar`. They all call `cancel::check` so a timed out part stops searching.
+
+use crate::cancel;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Route found by a search and its total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    /// Every node of the route, from the start up to and including the goal.
+    pub nodes: Vec<N>,
+}
+
+/// Every node reached by a search, with its cost and the node it was
+/// reached from, so the route to any of them can be reconstructed.
+#[derive(Debug, Clone)]
+pub struct Explored<N, C> {
+    nodes: Vec<(N, C, Option<usize>)>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Explored<N, C> {
+    fn new(start: N, cost: C) -> Self {
+        Self {
+            indices: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start, cost, None)],
+        }
+    }
+
+    /// Cost of the cheapest route found to the node, `None` if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.indices.get(node).map(|index| self.nodes[*index].1)
+    }
+
+    /// Cheapest route found to the node, `None` if it was not reached.
+    pub fn path(&self, node: &N) -> Option<Path<N, C>> {
+        self.indices.get(node).map(|index| self.path_of(*index))
+    }
+
+    /// Every reached node with its cost, in the order they were reached.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, cost, _)| (node, *cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn path_of(&self, index: usize) -> Path<N, C> {
+        let mut nodes = Vec::new();
+        let mut current = Some(index);
+        while let Some(index) = current {
+            nodes.push(self.nodes[index].0.clone());
+            current = self.nodes[index].2;
+        }
+        nodes.reverse();
+        Path {
+            cost: self.nodes[index].1,
+            nodes,
+        }
+    }
+
+    /// Record the node as reached from `parent` at `cost`. Returns its index
+    /// when it was not reached before or `cost` is cheaper than before.
+    fn reach(&mut self, node: N, cost: C, parent: usize) -> Option<usize>
+    where
+        C: Ord,
+    {
+        match self.indices.entry(node) {
+            Entry::Vacant(entry) => {
+                let index = self.nodes.len();
+                self.nodes.push((entry.key().clone(), cost, Some(parent)));
+                entry.insert(index);
+                Some(index)
+            }
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                let known = &mut self.nodes[index];
+                (cost < known.1).then(|| {
+                    (known.1, known.2) = (cost, Some(parent));
+                    index
+                })
+            }
+        }
+    }
+}
+
+/// Breadth-first search from `start` to the first node matching `is_goal`,
+/// every step costing 1. `None` if no goal is reachable.
+pub fn bfs<N, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (explored, goal) = breadth_first(start, successors, &mut is_goal);
+    goal.map(|index| explored.path_of(index))
+}
+
+/// Breadth-first search of every node reachable from `start`, with the
+/// number of steps to each of them.
+pub fn explore<N, I>(start: N, successors: impl FnMut(&N) -> I) -> Explored<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(start, successors, &mut |_| false).0
+}
+
+fn breadth_first<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    is_goal: &mut impl FnMut(&N) -> bool,
+) -> (Explored<N, usize>, Option<usize>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut explored = Explored::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+    while let Some(index) = queue.pop_front() {
+        cancel::check();
+        let (node, cost, _) = &explored.nodes[index];
+        if is_goal(node) {
+            return (explored, Some(index));
+        }
+
+        let (node, cost) = (node.clone(), *cost);
+        for next in successors(&node) {
+            queue.extend(explored.reach(next, cost + 1, index));
+        }
+    }
+    (explored, None)
+}
+
+/// Cheapest route from `start` to the first node matching `is_goal`, the
+/// successors being paired with the cost of the step to them.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like `dijkstra`, guided by a `heuristic` estimate of the remaining cost
+/// to a goal. The route is the cheapest one as long as the heuristic never
+/// overestimates.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut explored = Explored::new(start, C::default());
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        cancel::check();
+        let (node, known, _) = &explored.nodes[index];
+        if cost > *known {
+            // A cheaper route to the node was found after this one was queued.
+            continue;
+        }
+        if is_goal(node) {
+            return Some(explored.path_of(index));
+        }
+
+        let node = node.clone();
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if let Some(next) = explored.reach(next, cost, index) {
+                let estimate = cost + heuristic(&explored.nodes[next].0);
+                heap.push(Reverse((estimate, cost, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+const MAZE: &str = "S.#.\n..#.\n...G";
+
+#[cfg(test)]
+fn maze_steps(position: &(usize, usize)) -> Vec<(usize, usize)> {
+    let grid = crate::grid::Grid::parse(MAZE, |_, c| c).unwrap();
+    let steps = grid
+        .neighbours4(*position)
+        .filter(|next| grid[*next] != '#');
+    steps.collect()
+}
+
+#[test]
+fn test_bfs() {
+    let path = bfs((0, 0), maze_steps, |position| *position == (3, 2)).unwrap();
+    assert_eq!(path.cost, 5);
+    assert_eq!(path.nodes.len(), 6);
+    assert_eq!(path.nodes.first(), Some(&(0, 0)));
+    assert_eq!(path.nodes.last(), Some(&(3, 2)));
+    assert_eq!(
+        bfs((0, 0), maze_steps, |position| *position == (2, 0)),
+        None
+    );
+
+    let explored = explore((0, 0), maze_steps);
+    assert_eq!(explored.len(), 10);
+    assert_eq!(explored.cost(&(3, 0)), Some(7));
+    assert_eq!(explored.path(&(3, 0)).unwrap().nodes.len(), 8);
+}
+
+#[test]
+fn test_dijkstra() {
+    // The direct edge is more expensive than the detour.
+    let edges = |node: &char| match node {
+        'a' => vec![('b', 1), ('d', 10)],
+        'b' => vec![('c', 2)],
+        'c' => vec![('d', 3)],
+        _ => vec![],
+    };
+    let path = dijkstra('a', edges, |node| *node == 'd').unwrap();
+    assert_eq!(path.cost, 6);
+    assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd']);
+
+    let weighted =
+        |position: &(usize, usize)| maze_steps(position).into_iter().map(|next| (next, 1));
+    let goal = (3usize, 2usize);
+    let heuristic = |(x, y): &(usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+    let path = astar((0, 0), weighted, heuristic, |position| *position == goal);
+    assert_eq!(path.map(|path| path.cost), Some(5));
+}
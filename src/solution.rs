@@ -0,0 +1,283 @@
+use crate::error::Error;
+use crate::reader::Reader;
+use std::any::Any;
+use std::fmt;
+
+/// Typed answer returned by a puzzle part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    /// Rows of characters forming a picture, e.g. the day 10 CRT screen.
+    Image(Vec<String>),
+    /// The part has no answer, e.g. day 25 which only has a single part.
+    None,
+}
+
+impl Answer {
+    /// Name of the variant used in machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Unsigned(_) => "unsigned",
+            Self::Signed(_) => "signed",
+            Self::Text(_) => "text",
+            Self::Image(_) => "image",
+            Self::None => "none",
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Signed(value as i64)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Self::Signed(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(n) => write!(f, "{}", n),
+            Self::Signed(n) => write!(f, "{}", n),
+            Self::Text(text) => write!(f, "{}", text),
+            Self::Image(rows) => write!(f, "{}", rows.join("\n")),
+            Self::None => write!(f, "-"),
+        }
+    }
+}
+
+/// Named numeric parameter of a solver with its default value for one part,
+/// e.g. the number of rounds to play. Parameters used by both parts are
+/// declared once per part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub part: u8,
+    pub default: i64,
+    /// Smallest and largest values the solver accepts, overrides outside of
+    /// them are rejected on the command line.
+    pub min: i64,
+    pub max: i64,
+    pub description: &'static str,
+}
+
+/// Parameter values of a single part, the declared defaults unless overridden.
+#[derive(Debug, Clone, Copy)]
+pub struct Params<'a> {
+    declared: &'static [Param],
+    part: u8,
+    /// Values by name, later entries take precedence.
+    overrides: &'a [(String, i64)],
+}
+
+impl<'a> Params<'a> {
+    pub fn new(declared: &'static [Param], part: u8, overrides: &'a [(String, i64)]) -> Self {
+        Self {
+            declared,
+            part,
+            overrides,
+        }
+    }
+
+    /// The defaults of the given part.
+    pub fn defaults(declared: &'static [Param], part: u8) -> Self {
+        Self::new(declared, part, &[])
+    }
+
+    /// Whether any override applies to a parameter of the part, in which
+    /// case its answer may differ from the one expected with the defaults.
+    pub fn is_overridden(&self) -> bool {
+        self.overrides.iter().any(|(name, _)| {
+            self.declared
+                .iter()
+                .any(|param| param.name == *name && param.part == self.part)
+        })
+    }
+
+    /// Value of the parameter converted to the type used by the solver.
+    ///
+    /// Panics if the part does not declare the parameter or the value does
+    /// not fit into the type.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let param = self
+            .declared
+            .iter()
+            .find(|param| param.name == name && param.part == self.part)
+            .unwrap_or_else(|| panic!("Part {} has no parameter '{}'", self.part, name));
+        let value = self
+            .overrides
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map_or(param.default, |(_, value)| *value);
+        T::try_from(value)
+            .unwrap_or_else(|_| panic!("Value {} is out of range for '{}'", value, name))
+    }
+}
+
+/// Common interface implemented by every day.
+///
+/// ## Example
+/// ```rs
+/// pub struct Day1;
+///
+/// impl Solution for Day1 {
+///     type Input = Vec<String>;
+///     const DAY: u8 = 1;
+///     const TITLE: &'static str = "Calorie Counting";
+///
+///     fn parse(input: reader::Reader) -> Result<Self::Input, Error> {
+///         Ok(input.split_on_empty_line())
+///     }
+///
+///     fn part_one(input: Self::Input) -> Answer {
+///         part_one(input).into()
+///     }
+///
+///     fn part_two(input: Self::Input) -> Answer {
+///         part_two(input).into()
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// Named parameters of the solvers, such as the number of rounds to play,
+    /// which can be overridden from the command line. The example answer
+    /// files set the values their examples use, e.g. `row = 10` for day 15.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: Reader) -> Result<Self::Input, Error>;
+    fn part_one(input: Self::Input) -> Answer;
+    fn part_two(input: Self::Input) -> Answer;
+
+    /// Solve part one with the given parameter values. Days declaring
+    /// `PARAMS` implement this and solve `part_one` with the defaults.
+    fn part_one_with(input: Self::Input, _params: &Params) -> Answer {
+        Self::part_one(input)
+    }
+
+    /// Solve part two with the given parameter values, see `part_one_with`.
+    fn part_two_with(input: Self::Input, _params: &Params) -> Answer {
+        Self::part_two(input)
+    }
+}
+
+/// Object safe version of `Solution` used by the day registry.
+///
+/// Implemented for every `Solution`, the parsed input is passed around as
+/// `Box<dyn Any>` and downcast back to `Solution::Input` when solving.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn parse(&self, input: Reader) -> Result<Box<dyn Any + Send>, Error>;
+    /// Parameters declared by the solvers of the day.
+    fn params(&self) -> &'static [Param];
+
+    /// Solve a part with the parameter overrides, later entries take
+    /// precedence. Overrides of parameters the part does not declare are
+    /// ignored.
+    fn solve_with(&self, part: u8, input: Box<dyn Any + Send>, params: &[(String, i64)]) -> Answer;
+
+    /// Solve a part with the default parameters.
+    fn solve(&self, part: u8, input: Box<dyn Any + Send>) -> Answer {
+        self.solve_with(part, input, &[])
+    }
+
+    /// Name of the puzzle input file within an input directory.
+    fn input_file(&self) -> String {
+        format!("day{}.txt", self.day())
+    }
+}
+
+impl<T> Puzzle for T
+where
+    T: Solution + Sync,
+    T::Input: Send + 'static,
+{
+    fn day(&self) -> u8 {
+        T::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        T::TITLE
+    }
+
+    fn parse(&self, input: Reader) -> Result<Box<dyn Any + Send>, Error> {
+        Ok(Box::new(T::parse(input)?))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        T::PARAMS
+    }
+
+    fn solve_with(&self, part: u8, input: Box<dyn Any + Send>, params: &[(String, i64)]) -> Answer {
+        let input = *input
+            .downcast::<T::Input>()
+            .expect("input was not parsed by the same solution");
+        let params = Params::new(T::PARAMS, part, params);
+        match part {
+            1 => T::part_one_with(input, &params),
+            2 => T::part_two_with(input, &params),
+            _ => panic!("Unknown part: {:?}", part),
+        }
+    }
+}
+
+#[test]
+fn test_params() {
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "rounds",
+            part: 1,
+            default: 20,
+            min: i64::MIN,
+            max: i64::MAX,
+            description: "",
+        },
+        Param {
+            name: "rounds",
+            part: 2,
+            default: 10_000,
+            min: i64::MIN,
+            max: i64::MAX,
+            description: "",
+        },
+    ];
+    assert_eq!(Params::defaults(PARAMS, 2).get::<usize>("rounds"), 10_000);
+
+    let overrides = [("rounds".to_string(), 5), ("rounds".to_string(), -1)];
+    assert_eq!(
+        Params::new(PARAMS, 1, &overrides[..1]).get::<u32>("rounds"),
+        5
+    );
+    assert_eq!(Params::new(PARAMS, 1, &overrides).get::<i64>("rounds"), -1);
+    let result =
+        std::panic::catch_unwind(|| Params::new(PARAMS, 1, &overrides).get::<usize>("rounds"));
+    assert!(result.is_err());
+
+    assert!(!Params::defaults(PARAMS, 1).is_overridden());
+    assert!(Params::new(PARAMS, 2, &overrides).is_overridden());
+    let other = [("row".to_string(), 10)];
+    assert!(!Params::new(PARAMS, 1, &other).is_overridden());
+}
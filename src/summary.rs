@@ -0,0 +1,208 @@
+use crate::runner::Failure;
+
+/// Exit code when every day succeeded.
+pub const EXIT_SUCCESS: u8 = 0;
+/// Exit code when a day could not be read or parsed, or an answer was wrong.
+pub const EXIT_FAILURE: u8 = 1;
+/// Exit code for invalid command-line arguments.
+pub const EXIT_USAGE: u8 = 2;
+/// Exit code when a day panicked.
+pub const EXIT_PANIC: u8 = 3;
+/// Exit code when a day timed out.
+pub const EXIT_TIMEOUT: u8 = 4;
+
+/// Outcome of a single day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Day {
+    profile: Option<String>,
+    day: u8,
+    /// Failures of the parts of the day, in order and without repeats.
+    failures: Vec<Failure>,
+}
+
+impl Day {
+    /// Most severe failure of the parts, the one the day is counted under.
+    fn failure(&self) -> Option<&Failure> {
+        self.failures
+            .iter()
+            .max_by_key(|failure| exit_code(failure))
+    }
+}
+
+/// Exit code for a single failure.
+fn exit_code(failure: &Failure) -> u8 {
+    match failure {
+        Failure::Timeout(_) => EXIT_TIMEOUT,
+        Failure::Panic(_) => EXIT_PANIC,
+        Failure::Error(_) | Failure::Mismatch(_) => EXIT_FAILURE,
+    }
+}
+
+/// Outcome of every day that was run, printed at the end of a run and used
+/// for the exit code.
+#[derive(Debug, Default)]
+pub struct Summary {
+    days: Vec<Day>,
+}
+
+impl Summary {
+    /// Record the outcome of a part. Parts of the same day are expected to be
+    /// added one after the other, the day keeps the failures of every part.
+    pub fn add(&mut self, profile: Option<&str>, day: u8, failure: Option<&Failure>) {
+        let last = match self.days.last_mut() {
+            Some(last) if last.profile.as_deref() == profile && last.day == day => last,
+            _ => {
+                self.days.push(Day {
+                    profile: profile.map(String::from),
+                    day,
+                    failures: Vec::new(),
+                });
+                self.days.last_mut().unwrap()
+            }
+        };
+        if let Some(failure) = failure.filter(|failure| !last.failures.contains(failure)) {
+            last.failures.push(failure.clone());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    /// Whether any of the days failed or panicked.
+    pub fn has_failures(&self) -> bool {
+        self.days.iter().any(|day| !day.failures.is_empty())
+    }
+
+    /// `EXIT_TIMEOUT` if any part timed out, otherwise `EXIT_PANIC` if any part
+    /// panicked, `EXIT_FAILURE` if any part failed, `EXIT_SUCCESS` if none did.
+    pub fn exit_code(&self) -> u8 {
+        let failures = self.days.iter().flat_map(|day| &day.failures);
+        failures.map(exit_code).max().unwrap_or(EXIT_SUCCESS)
+    }
+
+    /// Print the counts, the succeeded days per profile and every failure.
+    /// Days are counted under their most severe failure.
+    pub fn print(&self) {
+        let count = |f: fn(&Option<&Failure>) -> bool| {
+            self.days.iter().filter(|day| f(&day.failure())).count()
+        };
+        println!(
+            "\nSummary: {} succeeded, {} failed, {} panicked, {} timed out.",
+            count(|failure| failure.is_none()),
+            count(|failure| { matches!(failure, Some(Failure::Error(_) | Failure::Mismatch(_))) }),
+            count(|failure| matches!(failure, Some(Failure::Panic(_)))),
+            count(|failure| matches!(failure, Some(Failure::Timeout(_)))),
+        );
+
+        let mut profiles = self
+            .days
+            .iter()
+            .map(|day| &day.profile)
+            .collect::<Vec<&Option<String>>>();
+        profiles.dedup();
+        for profile in profiles {
+            let succeeded = self
+                .days
+                .iter()
+                .filter(|day| &day.profile == profile && day.failures.is_empty())
+                .map(|day| day.day)
+                .collect::<Vec<u8>>();
+            if !succeeded.is_empty() {
+                println!("\tSucceeded{}: {}", label(profile), format_days(&succeeded));
+            }
+        }
+
+        for (day, failure) in self
+            .days
+            .iter()
+            .flat_map(|day| day.failures.iter().map(move |failure| (day, failure)))
+        {
+            match failure {
+                Failure::Error(_) | Failure::Mismatch(_) => {
+                    let error = failure.to_string();
+                    let error = error.lines().next().unwrap_or_default();
+                    println!(
+                        "\tFailed{}: day {}: {}",
+                        label(&day.profile),
+                        day.day,
+                        error
+                    );
+                }
+                Failure::Panic(message) => {
+                    println!(
+                        "\tPanicked{}: day {}: {}",
+                        label(&day.profile),
+                        day.day,
+                        message
+                    );
+                }
+                Failure::Timeout(timeout) => {
+                    println!(
+                        "\tTimed out{}: day {}: after {:?}",
+                        label(&day.profile),
+                        day.day,
+                        timeout
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn label(profile: &Option<String>) -> String {
+    profile
+        .as_ref()
+        .map_or(String::new(), |profile| format!(" ({})", profile))
+}
+
+/// Format sorted day numbers as ranges, e.g. `1-3, 5, 7-8`.
+fn format_days(days: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+    for day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *day => *end = *day,
+            _ => ranges.push((*day, *day)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[test]
+fn test_format_days() {
+    assert_eq!(format_days(&[1, 2, 3, 5, 7, 8]), "1-3, 5, 7-8");
+    assert_eq!(format_days(&[25]), "25");
+    assert_eq!(format_days(&[]), "");
+}
+
+#[test]
+fn test_exit_code() {
+    let mut summary = Summary::default();
+    summary.add(None, 1, None);
+    summary.add(None, 1, None);
+    assert_eq!((summary.len(), summary.exit_code()), (1, EXIT_SUCCESS));
+
+    summary.add(None, 4, Some(&Failure::Error("bad input".to_string())));
+    assert_eq!(summary.exit_code(), EXIT_FAILURE);
+    summary.add(None, 4, Some(&Failure::Panic("other".to_string())));
+    assert_eq!((summary.len(), summary.exit_code()), (2, EXIT_PANIC));
+
+    summary.add(Some("alice"), 4, Some(&Failure::Panic("boom".to_string())));
+    assert_eq!((summary.len(), summary.exit_code()), (3, EXIT_PANIC));
+
+    let timeout = Failure::Timeout(std::time::Duration::from_secs(1));
+    summary.add(None, 16, Some(&timeout));
+    summary.add(None, 19, Some(&Failure::Panic("after".to_string())));
+    assert_eq!(summary.exit_code(), EXIT_TIMEOUT);
+}
@@ -0,0 +1,163 @@
+use crate::cli::Part;
+use crate::error::Error;
+use crate::inputs::Source;
+use crate::pool;
+use crate::runner::Failure;
+use crate::solution::Puzzle;
+use crate::summary::Summary;
+use std::time::{Duration, Instant};
+
+/// Measured durations of a single day.
+pub struct Timing {
+    day: u8,
+    title: &'static str,
+    parse: Duration,
+    parts: [Option<Duration>; 2],
+}
+
+impl Timing {
+    fn total(&self) -> Duration {
+        self.parse + self.parts.iter().flatten().sum::<Duration>()
+    }
+}
+
+/// Time the parser and the selected parts of every given day and print the
+/// results as a table sorted by total duration.
+///
+/// With `preload` every input file is read before any timer is started, so
+/// the parse times do not include file I/O. Days whose input cannot be read
+/// or parsed, or that panic, are reported and left out of the table. Every day
+/// is recorded in the summary under the given profile label. The parameter
+/// overrides take precedence over the ones of the source.
+pub fn run(
+    puzzles: &[&dyn Puzzle],
+    part: Part,
+    preload: bool,
+    source: &Source,
+    profile: Option<&str>,
+    params: &[(String, i64)],
+    summary: &mut Summary,
+) {
+    let inputs = puzzles
+        .iter()
+        .map(|puzzle| match preload {
+            true => source.load(*puzzle).map(Source::Reader).map(Some),
+            false => Ok(None),
+        })
+        .collect::<Vec<Result<Option<Source>, Error>>>();
+
+    let mut timings = Vec::with_capacity(puzzles.len());
+    for (puzzle, input) in puzzles.iter().zip(inputs) {
+        let result = input
+            .map_err(|error| Failure::Error(error.to_string()))
+            .and_then(|input| {
+                let params = [source.params(*puzzle), params].concat();
+                let source = input.as_ref().unwrap_or(source);
+                match pool::catch(|| measure(*puzzle, part, source, &params)) {
+                    Ok(result) => result.map_err(|error| Failure::Error(error.to_string())),
+                    Err(message) => Err(Failure::Panic(message)),
+                }
+            });
+        match result {
+            Ok(timing) => {
+                summary.add(profile, puzzle.day(), None);
+                timings.push(timing);
+            }
+            Err(failure) => {
+                eprintln!("Day {}: {}", puzzle.day(), failure);
+                summary.add(profile, puzzle.day(), Some(&failure));
+            }
+        }
+    }
+    print_table(timings);
+}
+
+/// Time the parser and the selected parts of the given day.
+///
+/// The input is parsed separately for each part, only the first parse is
+/// included in the timing.
+pub fn measure(
+    puzzle: &dyn Puzzle,
+    part: Part,
+    source: &Source,
+    params: &[(String, i64)],
+) -> Result<Timing, Error> {
+    let start = Instant::now();
+    let parsed = puzzle.parse(source.load(puzzle)?)?;
+    let parse = start.elapsed();
+
+    let mut parsed = Some(parsed);
+    let mut parts = [None, None];
+    for n in (1..=2).filter(|n| part.includes(*n)) {
+        let input = match parsed.take() {
+            Some(parsed) => parsed,
+            None => puzzle.parse(source.load(puzzle)?)?,
+        };
+        let start = Instant::now();
+        puzzle.solve_with(n, input, params);
+        parts[n as usize - 1] = Some(start.elapsed());
+    }
+
+    Ok(Timing {
+        day: puzzle.day(),
+        title: puzzle.title(),
+        parse,
+        parts,
+    })
+}
+
+fn print_table(mut timings: Vec<Timing>) {
+    timings.sort_by_key(|timing| std::cmp::Reverse(timing.total()));
+    println!(
+        "{:>3}  {:<26}{:>11}{:>11}{:>11}{:>11}",
+        "Day", "Title", "Parse", "Part 1", "Part 2", "Total"
+    );
+    for timing in &timings {
+        println!(
+            "{:>3}  {:<26}{:>11}{:>11}{:>11}{:>11}",
+            timing.day,
+            timing.title,
+            format_duration(Some(timing.parse)),
+            format_duration(timing.parts[0]),
+            format_duration(timing.parts[1]),
+            format_duration(Some(timing.total())),
+        );
+    }
+
+    let sum =
+        |f: fn(&Timing) -> Option<Duration>| -> Duration { timings.iter().filter_map(f).sum() };
+    println!(
+        "{:>3}  {:<26}{:>11}{:>11}{:>11}{:>11}",
+        "",
+        "Total",
+        format_duration(Some(sum(|timing| Some(timing.parse)))),
+        format_duration(Some(sum(|timing| timing.parts[0]))),
+        format_duration(Some(sum(|timing| timing.parts[1]))),
+        format_duration(Some(sum(|timing| Some(timing.total())))),
+    );
+}
+
+/// Format a duration using the largest fitting unit, e.g. `1.25ms`.
+pub fn format_duration(duration: Option<Duration>) -> String {
+    let Some(duration) = duration else {
+        return "-".to_string();
+    };
+    let nanos = duration.as_nanos() as f64;
+    match nanos {
+        n if n < 1_000.0 => format!("{}ns", n),
+        n if n < 1_000_000.0 => format!("{:.2}µs", n / 1_000.0),
+        n if n < 1_000_000_000.0 => format!("{:.2}ms", n / 1_000_000.0),
+        n => format!("{:.2}s", n / 1_000_000_000.0),
+    }
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(None), "-");
+    assert_eq!(format_duration(Some(Duration::from_nanos(512))), "512ns");
+    assert_eq!(
+        format_duration(Some(Duration::from_micros(1_250))),
+        "1.25ms"
+    );
+    assert_eq!(format_duration(Some(Duration::from_millis(2_500))), "2.50s");
+}
@@ -0,0 +1,152 @@
+use crate::answers::Answers;
+use crate::cli::VerifyOptions;
+use crate::inputs::{Profile, Source};
+use crate::pool;
+use crate::runner::{self, Failure};
+use crate::solution::{Answer, Puzzle};
+
+/// Outcome of comparing a computed answer with the stored one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Recorded,
+    Error,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Fail => "FAIL",
+            Self::Missing => "MISSING",
+            Self::Recorded => "RECORDED",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+/// Solve the selected parts of every given day with the inputs of the profile
+/// and compare the answers with its answers file, printing one line per part
+/// and a summary.
+///
+/// With `record` the answers of parts that have no entry yet are added to the
+/// file. Returns whether every stored answer matched.
+pub fn run(
+    puzzles: &[&dyn Puzzle],
+    profile: &Profile,
+    options: &VerifyOptions,
+) -> Result<bool, String> {
+    let path = options
+        .answers
+        .clone()
+        .unwrap_or_else(|| profile.answers_path());
+    let mut answers = Answers::load(&path)?;
+    let source = Source::Profile(profile.clone());
+    let mut statuses = Vec::new();
+    for puzzle in puzzles {
+        for part in (1..=2).filter(|n| options.selection.part.includes(*n)) {
+            let (status, details) = match pool::catch(|| runner::solve(*puzzle, part, &source)) {
+                Ok(Ok(Answer::None)) => continue,
+                Ok(Ok(actual)) => compare(&mut answers, puzzle.day(), part, actual, options.record),
+                Ok(Err(error)) => (Status::Error, error.to_string()),
+                Err(message) => (Status::Error, Failure::Panic(message).to_string()),
+            };
+
+            let row = match details.contains('\n') {
+                true => format!("{}\n{}", status.label(), indent(&details)),
+                false => format!("{:<9}{}", status.label(), details),
+            };
+            println!("Day {:>2} part {}  {}", puzzle.day(), part, row);
+            statuses.push(status);
+        }
+    }
+
+    let count = |status| statuses.iter().filter(|s| **s == status).count();
+    println!(
+        "\n{} passed, {} failed, {} missing, {} recorded, {} errors.",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing),
+        count(Status::Recorded),
+        count(Status::Error)
+    );
+
+    if count(Status::Recorded) > 0 {
+        answers.save(&path)?;
+        println!("Saved answers to '{}'.", path);
+    }
+    Ok(count(Status::Fail) + count(Status::Error) == 0)
+}
+
+/// Compare the answer with the stored one, recording it if requested.
+fn compare(
+    answers: &mut Answers,
+    day: u8,
+    part: u8,
+    actual: Answer,
+    record: bool,
+) -> (Status, String) {
+    match answers.get(day, part) {
+        Some(expected) if expected.to_string() == actual.to_string() => {
+            (Status::Pass, actual.to_string())
+        }
+        Some(expected) => (Status::Fail, diff(expected, &actual)),
+        None if record => {
+            let details = actual.to_string();
+            answers.insert(day, part, actual);
+            (Status::Recorded, details)
+        }
+        None => (Status::Missing, actual.to_string()),
+    }
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Describe the difference between the expected and the actual answer, line
+/// by line for multi-line answers.
+pub fn diff(expected: &Answer, actual: &Answer) -> String {
+    let (expected, actual) = (expected.to_string(), actual.to_string());
+    if !expected.contains('\n') && !actual.contains('\n') {
+        return format!("expected {}, got {}", expected, actual);
+    }
+
+    let expected_lines = expected.lines().collect::<Vec<&str>>();
+    let actual_lines = actual.lines().collect::<Vec<&str>>();
+    let mut result = Vec::new();
+    for i in 0..std::cmp::max(expected_lines.len(), actual_lines.len()) {
+        match (expected_lines.get(i), actual_lines.get(i)) {
+            (Some(e), Some(a)) if e == a => result.push(format!("  {}", e)),
+            (e, a) => {
+                if let Some(e) = e {
+                    result.push(format!("- {}", e));
+                }
+                if let Some(a) = a {
+                    result.push(format!("+ {}", a));
+                }
+            }
+        }
+    }
+    result.join("\n")
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(
+        diff(&Answer::Unsigned(1), &Answer::Signed(2)),
+        "expected 1, got 2"
+    );
+    assert_eq!(
+        diff(
+            &Answer::Text("#.\n.#".to_string()),
+            &Answer::Text("#.\n##".to_string())
+        ),
+        "  #.\n- .#\n+ ##"
+    );
+}
@@ -0,0 +1,161 @@
+use crate::cli::{Part, WatchOptions};
+use crate::inputs::Profile;
+use crate::json::Json;
+use crate::timing::format_duration;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, SystemTime};
+
+/// Poll the source and input files of the day and re-run its tests and
+/// solution through cargo whenever one of them changes, printing a single
+/// line per run. Only returns on errors.
+pub fn run(day: u8, profile: &Profile, options: &WatchOptions) -> Result<(), String> {
+    println!(
+        "Watching src/day{}.rs and {}/day{}*.txt, press Ctrl-C to stop.",
+        day, profile.dir, day
+    );
+    let mut last = None;
+    let mut count = 0;
+    loop {
+        let current = snapshot(day, &profile.dir)?;
+        if last.as_ref() != Some(&current) {
+            count += 1;
+            println!("#{} day {}: {}", count, day, check(day, options)?);
+            last = Some(current);
+        }
+        std::thread::sleep(options.interval);
+    }
+}
+
+/// Modification times of the source file and the input files of the day.
+fn snapshot(day: u8, dir: &str) -> Result<Vec<(String, Option<SystemTime>)>, String> {
+    let modified = |path: &Path| path.metadata().and_then(|meta| meta.modified()).ok();
+    let source = format!("src/day{}.rs", day);
+    let mut files = vec![(source.clone(), modified(Path::new(&source)))];
+
+    let entries =
+        std::fs::read_dir(dir).map_err(|error| format!("Unable to read '{}': {}", dir, error))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_input_of(&name, day) {
+            files.push((name, modified(&entry.path())));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Whether the file name is an input of the day, e.g. `day9.txt` or
+/// `day9_test2.txt` for day 9 but not `day19.txt`.
+fn is_input_of(name: &str, day: u8) -> bool {
+    name.strip_prefix(&format!("day{}", day))
+        .and_then(|rest| rest.strip_suffix(".txt"))
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with('_'))
+}
+
+/// Run the tests and the solution of the day and describe the outcome.
+fn check(day: u8, options: &WatchOptions) -> Result<String, String> {
+    let tests = cargo(&["test", "--quiet", "--lib", &format!("day{}::", day)])?;
+    let tests = match test_counts(&String::from_utf8_lossy(&tests.stdout)) {
+        Some((passed, 0)) => format!("tests ok ({} passed)", passed),
+        Some((passed, failed)) => format!("tests FAILED ({} of {})", failed, passed + failed),
+        None => return Ok(format!("build FAILED: {}", build_error(&tests))),
+    };
+
+    let mut args = vec!["run", "--quiet", "--release", "--", "run"];
+    let day = day.to_string();
+    args.extend([day.as_str(), "--format", "json"]);
+    let selection = &options.selection;
+    match selection.part {
+        Part::One => args.extend(["-p", "1"]),
+        Part::Two => args.extend(["-p", "2"]),
+        Part::Both => (),
+    }
+    if let Some(dir) = &selection.input_dir {
+        args.extend(["--input-dir", dir]);
+    }
+    if let Some(profile) = &selection.profile {
+        args.extend(["--profile", profile]);
+    }
+    let solution = cargo(&args)?;
+    let parts = match String::from_utf8_lossy(&solution.stdout).parse::<Json>() {
+        Ok(Json::Array(records)) => records.iter().map(describe).collect::<Vec<String>>(),
+        _ => return Ok(format!("{}, run FAILED: {}", tests, build_error(&solution))),
+    };
+    Ok([tests]
+        .into_iter()
+        .chain(parts)
+        .collect::<Vec<String>>()
+        .join(", "))
+}
+
+fn cargo(args: &[&str]) -> Result<Output, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    Command::new(&cargo)
+        .args(args)
+        .output()
+        .map_err(|error| format!("Unable to run '{}': {}", cargo, error))
+}
+
+/// Passed and failed counts of the `test result:` line of `cargo test`.
+fn test_counts(stdout: &str) -> Option<(usize, usize)> {
+    let line = stdout
+        .lines()
+        .find(|line| line.starts_with("test result:"))?;
+    let count = |suffix: &str| {
+        line.split([';', '.'])
+            .find_map(|field| field.trim().strip_suffix(suffix)?.trim().parse().ok())
+    };
+    Some((count(" passed")?, count(" failed")?))
+}
+
+/// First error reported by cargo, with its location when there is one.
+fn build_error(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut lines = stderr.lines().skip_while(|line| !line.starts_with("error"));
+    match (lines.next(), lines.next().map(str::trim_start)) {
+        (Some(error), Some(location)) if location.starts_with("-->") => {
+            format!("{} ({})", error, location.trim_start_matches("--> "))
+        }
+        (Some(error), _) => error.to_string(),
+        (None, _) => format!("cargo exited with {}", output.status),
+    }
+}
+
+/// A JSON record of `run --format json` as `part N: answer (time)`.
+fn describe(record: &Json) -> String {
+    let part = record.get("part").and_then(Json::as_f64).unwrap_or(0.0);
+    if let Some(error) = record.get("error").and_then(Json::as_str) {
+        return format!(
+            "part {}: {}",
+            part,
+            error.lines().next().unwrap_or_default()
+        );
+    }
+    let answer = match record.get("answer") {
+        Some(Json::String(text)) => text.clone(),
+        Some(Json::Array(rows)) => format!("<image of {} rows>", rows.len()),
+        Some(answer) => answer.to_string(),
+        None => String::new(),
+    };
+    let elapsed = record
+        .get("elapsed_ns")
+        .and_then(Json::as_f64)
+        .map(|nanos| Duration::from_nanos(nanos as u64));
+    format!("part {}: {} ({})", part, answer, format_duration(elapsed))
+}
+
+#[test]
+fn test_is_input_of() {
+    assert!(is_input_of("day9.txt", 9));
+    assert!(is_input_of("day9_test2.txt", 9));
+    assert!(!is_input_of("day19.txt", 9));
+    assert!(!is_input_of("day9.rs", 9));
+}
+
+#[test]
+fn test_test_counts() {
+    let stdout = "\nrunning 3 tests\n..F\ntest result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 70 filtered out; finished in 0.00s\n";
+    assert_eq!(test_counts(stdout), Some((2, 1)));
+    assert_eq!(test_counts("error: could not compile"), None);
+}
@@ -0,0 +1,124 @@
+use advent_of_code_2022::inputs::{Example, Source};
+use advent_of_code_2022::reader::{self, Reader};
+use advent_of_code_2022::runner::{self, Failure, Task};
+use advent_of_code_2022::solution::{Answer, Puzzle};
+use advent_of_code_2022::summary::{self, Summary};
+use advent_of_code_2022::{day1, day25, day4, days, generate};
+
+fn solve(puzzle: &dyn Puzzle, part: u8, input: Reader) -> Answer {
+    puzzle.solve(part, puzzle.parse(input).unwrap())
+}
+
+#[test]
+fn test_registry() {
+    let numbers = days::DAYS
+        .iter()
+        .map(|puzzle| puzzle.day())
+        .collect::<Vec<u8>>();
+    assert_eq!(numbers, (1..=25).collect::<Vec<u8>>());
+    assert_eq!(days::get(25).unwrap().title(), "Full of Hot Air");
+    assert!(days::get(26).is_none());
+}
+
+#[test]
+fn test_solve_through_registry() {
+    let puzzle = days::get(1).unwrap();
+    let input = reader::open("files/day1_test.txt");
+    assert_eq!(solve(puzzle, 1, input.clone()), Answer::Unsigned(24000));
+    assert_eq!(solve(puzzle, 2, input), Answer::Unsigned(45000));
+}
+
+#[test]
+fn test_typed_solvers() {
+    let groups = reader::open("files/day1_test.txt").blocks();
+    let groups = groups.into_iter().map(|group| group.lines_as());
+    assert_eq!(day1::part_one(groups.collect()), 24000);
+
+    let sections = Reader::from("2-4,6-8\n2-8,3-7\n6-6,4-6")
+        .try_lines_as::<day4::Section>()
+        .unwrap();
+    assert_eq!(day4::part_one(sections), 2);
+}
+
+#[test]
+fn test_parse_error() {
+    let error = days::get(4)
+        .unwrap()
+        .parse(Reader::from("2-4,6-8\n2-x,3-7"))
+        .unwrap_err();
+    assert!(error.to_string().starts_with("<input>:2: "), "{}", error);
+}
+
+#[test]
+fn test_unreachable_goal() {
+    // The end is too high to climb to from anywhere.
+    let source = Source::Reader(Reader::from("SbE"));
+    let mut summary = Summary::default();
+    for part in 1..=2 {
+        let task = Task {
+            profile: None,
+            source: &source,
+            puzzle: days::get(12).unwrap(),
+            part,
+            timeout: None,
+            params: &[],
+        };
+        let outcome = runner::solve_task(&task);
+        assert!(matches!(outcome, Err(Failure::Panic(_))), "{:?}", outcome);
+        summary.add(None, 12, outcome.as_ref().err());
+    }
+    assert_eq!(summary.exit_code(), summary::EXIT_PANIC);
+}
+
+#[test]
+fn test_example_with_params() {
+    let source = Source::Example(Example::load(1).unwrap());
+    let params = [("row".to_string(), 11)];
+    let task = |params| Task {
+        profile: None,
+        source: &source,
+        puzzle: days::get(15).unwrap(),
+        part: 1,
+        timeout: None,
+        params,
+    };
+    let answer = |outcome: runner::Outcome| outcome.map(|(answer, _)| answer);
+    assert_eq!(
+        answer(runner::solve_task(&task(&[]))),
+        Ok(Answer::Signed(26))
+    );
+    // The expected answer is for row 10, other rows are not compared.
+    assert_eq!(
+        answer(runner::solve_task(&task(&params))),
+        Ok(Answer::Signed(28))
+    );
+}
+
+#[test]
+fn test_snafu() {
+    for (decimal, snafu) in [
+        (1, "1"),
+        (3, "1="),
+        (2022, "1=11-2"),
+        (314159265, "1121-1110-1=0"),
+    ] {
+        assert_eq!(day25::to_base_5(decimal), snafu);
+        assert_eq!(day25::from_base_5(snafu), decimal);
+    }
+}
+
+#[test]
+fn test_generated_inputs() {
+    for puzzle in days::DAYS {
+        let size = Some(generate::test_size(puzzle.day()).unwrap());
+        let input = generate::input(puzzle.day(), 3, size).unwrap();
+        assert_eq!(input, generate::input(puzzle.day(), 3, size).unwrap());
+        for part in 1..=2 {
+            let answer = solve(*puzzle, part, Reader::from(input.as_str()));
+            // Day 25 has no second part.
+            let has_answer = (puzzle.day(), part) != (25, 2);
+            let message = format!("day {} part {}", puzzle.day(), part);
+            assert_eq!(answer.kind() != "none", has_answer, "{}", message);
+        }
+    }
+}